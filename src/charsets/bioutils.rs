@@ -1,5 +1,50 @@
 use super::*;
 
+/// 256-bit membership table with one bit per byte value, so checking a byte is a shift and a mask instead of a search through the charset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BioUtilsByteSet(pub [u64; 4]);
+
+impl BioUtilsByteSet {
+    /// Builds the membership table of a charset. Usable in const items so tables are built at compile time.
+    pub const fn new(charset: &[u8]) -> BioUtilsByteSet {
+        let mut bits = [0u64; 4];
+        let mut i = 0;
+        while i < charset.len() {
+            bits[(charset[i] >> 6) as usize] |= 1 << (charset[i] & 63);
+            i += 1;
+        }
+        BioUtilsByteSet(bits)
+    }
+    /// Checks if the byte is in the charset.
+    #[inline]
+    pub const fn contains(&self, byte: u8) -> bool {
+        (self.0[(byte >> 6) as usize] >> (byte & 63)) & 1 == 1
+    }
+}
+
+/// Builds a 256-byte recode table indexed by the byte to recode. Bytes outside the charset map to COMPLEMENT_FILLER.
+pub const fn recode_table(charset: &[u8], recode: &[u8]) -> [u8; 256] {
+    let members = BioUtilsByteSet::new(charset);
+    let mut table = [COMPLEMENT_FILLER; 256];
+    let mut i = 0;
+    while i < recode.len() && i < 256 {
+        if members.contains(i as u8) {
+            table[i] = recode[i];
+        }
+        i += 1;
+    }
+    table
+}
+
+// Promotes a const table built from the charset to a 'static reference.
+macro_rules! byte_set {
+    ($charset:expr) => {{ const TABLE: BioUtilsByteSet = BioUtilsByteSet::new(&$charset); &TABLE }};
+}
+
+macro_rules! recode_table {
+    ($charset:expr, $recode:expr) => {{ const TABLE: [u8; 256] = recode_table(&$charset, &$recode); &TABLE }};
+}
+
 pub enum BioUtilsCharSet {
     Letters,
     LettersUppercase,
@@ -70,6 +115,42 @@ impl BioUtilsCharSet {
             BioUtilsCharSet::Percent => &PERCENT,
         }
     }
+    /// Const 256-bit membership table of the charset.
+    pub const fn table(&self) -> &'static BioUtilsByteSet {
+        match *self {
+            BioUtilsCharSet::Letters => byte_set!(ASCII_LETTERS),
+            BioUtilsCharSet::LettersUppercase => byte_set!(ASCII_LETTERS_UPPERCASE),
+            BioUtilsCharSet::LettersLowercase => byte_set!(ASCII_LETTERS_LOWERCASE),
+            BioUtilsCharSet::Nucleotide => byte_set!(IUPAC_NUCLEOTIDE),
+            BioUtilsCharSet::Dna => byte_set!(DNA),
+            BioUtilsCharSet::DnaMixCase => byte_set!(DNA_MIX_CASE),
+            BioUtilsCharSet::Dnan => byte_set!(DNAN),
+            BioUtilsCharSet::DnanMixCase => byte_set!(DNAN_MIX_CASE),
+            BioUtilsCharSet::DnaLowercase => byte_set!(DNA_LOWERCASE),
+            BioUtilsCharSet::Rna => byte_set!(RNA),
+            BioUtilsCharSet::RnaMixCase => byte_set!(RNA_MIX_CASE),
+            BioUtilsCharSet::Rnan => byte_set!(RNAN),
+            BioUtilsCharSet::RnanMixCase => byte_set!(RNAN_MIX_CASE),
+            BioUtilsCharSet::RnaLowercase => byte_set!(RNA_LOWERCASE),
+            BioUtilsCharSet::Gap => byte_set!(GAP),
+            BioUtilsCharSet::N => byte_set!(N),
+            BioUtilsCharSet::NMixCase => byte_set!(N_MIX_CASE),
+            BioUtilsCharSet::Gc => byte_set!(GC),
+            BioUtilsCharSet::GcMixCase => byte_set!(GC_MIX_CASE),
+            BioUtilsCharSet::AminoAcid => byte_set!(AMINO_ACID),
+            BioUtilsCharSet::DnaComplement => byte_set!(DNA_COMPLEMENT),
+            BioUtilsCharSet::DnaComplementMixCase => byte_set!(DNA_COMPLEMENT_MIX_CASE),
+            BioUtilsCharSet::Phred33Encode => byte_set!(PHRED33_ENCODE),
+            BioUtilsCharSet::Phred33Decode => byte_set!(PHRED33_DECODE),
+            BioUtilsCharSet::Phred33Score => byte_set!(PHRED33_SCORE),
+            BioUtilsCharSet::Phred64Encode => byte_set!(PHRED64_ENCODE),
+            BioUtilsCharSet::Phred64Decode => byte_set!(PHRED64_DECODE),
+            BioUtilsCharSet::Phred64Score => byte_set!(PHRED64_SCORE),
+            BioUtilsCharSet::SolexaEncode => byte_set!(SOLEXA_ENCODE),
+            BioUtilsCharSet::SangerEncode => byte_set!(SANGER_ENCODE),
+            BioUtilsCharSet::Percent => byte_set!(PERCENT),
+        }
+    }
 }
 
 pub enum BioUtilsUsizeSet {
@@ -101,10 +182,36 @@ pub enum BioUtilsRecodeSet {
     Phred33Decode,
     Phred64Encode,
     Phred64Decode,
+    /// Solexa+64 to Phred+33 with the log-odds transform, clamped to Phred33 score 42.
+    Solexa64ToPhred33,
+    /// Phred+64 to Phred+33, clamped to Phred33 score 42.
+    Phred64ToPhred33,
+    /// Phred+33 to Phred+64.
+    Phred33ToPhred64,
     DnaComplement,
     DnanComplement,
     DnaComplementMixCase,
     DnanComplementMixCase,
+    /// DNA to RNA, T to U of either case.
+    Transcribe,
+    /// RNA to DNA, U to T of either case.
+    ReverseTranscribe,
+    RnaComplement,
+    RnanComplement,
+    RnaComplementMixCase,
+    RnanComplementMixCase,
+    /// Complement of uppercase IUPAC nucleotide codes, R-Y, K-M, B-V, D-H, and S, W, N and gaps unchanged.
+    IupacComplement,
+    /// Complement of IUPAC nucleotide codes of either case.
+    IupacComplementMixCase,
+    /// Bisulfite conversion of the top strand, every C to T.
+    BisulfiteTop,
+    /// Bisulfite conversion of the bottom strand on top strand coordinates, every G to A.
+    BisulfiteBottom,
+    /// Bisulfite conversion of the top strand with methylated CpG, C to T except the C of CpG.
+    BisulfiteTopCpg,
+    /// Bisulfite conversion of the bottom strand with methylated CpG, G to A except the G of CpG.
+    BisulfiteBottomCpg,
 }
 
 pub struct BioUtilsRecodeStruct<'a> {
@@ -113,16 +220,133 @@ pub struct BioUtilsRecodeStruct<'a> {
 }
 
 impl BioUtilsRecodeSet {
-    pub const fn value(&self) -> BioUtilsRecodeStruct {
+    pub const fn value(&self) -> BioUtilsRecodeStruct<'_> {
         match *self {
             BioUtilsRecodeSet::Phred33Encode => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::Phred33Score.value(), recode: BioUtilsCharSet::Phred33Encode.value(), },
             BioUtilsRecodeSet::Phred33Decode => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::Phred33Encode.value(), recode: BioUtilsCharSet::Phred33Decode.value(), },
             BioUtilsRecodeSet::Phred64Encode => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::Phred64Score.value(), recode: BioUtilsCharSet::Phred64Encode.value(), },
             BioUtilsRecodeSet::Phred64Decode => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::Phred64Encode.value(), recode: BioUtilsCharSet::Phred64Decode.value(), },
+            BioUtilsRecodeSet::Solexa64ToPhred33 => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::SolexaEncode.value(), recode: &SOLEXA_TO_PHRED33, },
+            BioUtilsRecodeSet::Phred64ToPhred33 => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::Phred64Encode.value(), recode: &PHRED64_TO_PHRED33, },
+            BioUtilsRecodeSet::Phred33ToPhred64 => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::Phred33Encode.value(), recode: &PHRED33_TO_PHRED64, },
             BioUtilsRecodeSet::DnaComplement => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::Dna.value(), recode: BioUtilsCharSet::DnaComplement.value(), },
             BioUtilsRecodeSet::DnanComplement => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::Dnan.value(), recode: BioUtilsCharSet::DnaComplement.value(), },
             BioUtilsRecodeSet::DnaComplementMixCase => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::DnaMixCase.value(), recode: BioUtilsCharSet::DnaComplementMixCase.value(), },
             BioUtilsRecodeSet::DnanComplementMixCase => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::DnanMixCase.value(), recode: BioUtilsCharSet::DnaComplementMixCase.value(), },
+            BioUtilsRecodeSet::Transcribe => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::DnanMixCase.value(), recode: &TRANSCRIBE, },
+            BioUtilsRecodeSet::ReverseTranscribe => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::RnanMixCase.value(), recode: &REVERSE_TRANSCRIBE, },
+            BioUtilsRecodeSet::RnaComplement => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::Rna.value(), recode: &RNA_COMPLEMENT, },
+            BioUtilsRecodeSet::RnanComplement => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::Rnan.value(), recode: &RNA_COMPLEMENT, },
+            BioUtilsRecodeSet::RnaComplementMixCase => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::RnaMixCase.value(), recode: &RNA_COMPLEMENT_MIX_CASE, },
+            BioUtilsRecodeSet::RnanComplementMixCase => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::RnanMixCase.value(), recode: &RNA_COMPLEMENT_MIX_CASE, },
+            BioUtilsRecodeSet::IupacComplement => BioUtilsRecodeStruct{ charset: &IUPAC_NUCLEOTIDE_UPPERCASE, recode: &IUPAC_COMPLEMENT_MIX_CASE, },
+            BioUtilsRecodeSet::IupacComplementMixCase => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::Nucleotide.value(), recode: &IUPAC_COMPLEMENT_MIX_CASE, },
+            BioUtilsRecodeSet::BisulfiteTop | BioUtilsRecodeSet::BisulfiteTopCpg => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::DnanMixCase.value(), recode: &BISULFITE_TOP, },
+            BioUtilsRecodeSet::BisulfiteBottom | BioUtilsRecodeSet::BisulfiteBottomCpg => BioUtilsRecodeStruct{ charset: BioUtilsCharSet::DnanMixCase.value(), recode: &BISULFITE_BOTTOM, },
         }
     }
-}
\ No newline at end of file
+    /// Const 256-bit membership table of the bytes that can be recoded.
+    pub const fn charset_table(&self) -> &'static BioUtilsByteSet {
+        match *self {
+            BioUtilsRecodeSet::Phred33Encode => BioUtilsCharSet::Phred33Score.table(),
+            BioUtilsRecodeSet::Phred33Decode => BioUtilsCharSet::Phred33Encode.table(),
+            BioUtilsRecodeSet::Phred64Encode => BioUtilsCharSet::Phred64Score.table(),
+            BioUtilsRecodeSet::Phred64Decode => BioUtilsCharSet::Phred64Encode.table(),
+            BioUtilsRecodeSet::Solexa64ToPhred33 => BioUtilsCharSet::SolexaEncode.table(),
+            BioUtilsRecodeSet::Phred64ToPhred33 => BioUtilsCharSet::Phred64Encode.table(),
+            BioUtilsRecodeSet::Phred33ToPhred64 => BioUtilsCharSet::Phred33Encode.table(),
+            BioUtilsRecodeSet::DnaComplement => BioUtilsCharSet::Dna.table(),
+            BioUtilsRecodeSet::DnanComplement => BioUtilsCharSet::Dnan.table(),
+            BioUtilsRecodeSet::DnaComplementMixCase => BioUtilsCharSet::DnaMixCase.table(),
+            BioUtilsRecodeSet::DnanComplementMixCase => BioUtilsCharSet::DnanMixCase.table(),
+            BioUtilsRecodeSet::Transcribe => BioUtilsCharSet::DnanMixCase.table(),
+            BioUtilsRecodeSet::ReverseTranscribe => BioUtilsCharSet::RnanMixCase.table(),
+            BioUtilsRecodeSet::RnaComplement => BioUtilsCharSet::Rna.table(),
+            BioUtilsRecodeSet::RnanComplement => BioUtilsCharSet::Rnan.table(),
+            BioUtilsRecodeSet::RnaComplementMixCase => BioUtilsCharSet::RnaMixCase.table(),
+            BioUtilsRecodeSet::RnanComplementMixCase => BioUtilsCharSet::RnanMixCase.table(),
+            BioUtilsRecodeSet::IupacComplement => byte_set!(IUPAC_NUCLEOTIDE_UPPERCASE),
+            BioUtilsRecodeSet::IupacComplementMixCase => BioUtilsCharSet::Nucleotide.table(),
+            BioUtilsRecodeSet::BisulfiteTop | BioUtilsRecodeSet::BisulfiteBottom => BioUtilsCharSet::DnanMixCase.table(),
+            BioUtilsRecodeSet::BisulfiteTopCpg | BioUtilsRecodeSet::BisulfiteBottomCpg => BioUtilsCharSet::DnanMixCase.table(),
+        }
+    }
+    /// Const 256-byte recode table indexed by the byte to recode. Bytes outside the charset map to COMPLEMENT_FILLER.
+    pub const fn recode_table(&self) -> &'static [u8; 256] {
+        match *self {
+            BioUtilsRecodeSet::Phred33Encode => recode_table!(PHRED33_SCORE, PHRED33_ENCODE),
+            BioUtilsRecodeSet::Phred33Decode => recode_table!(PHRED33_ENCODE, PHRED33_DECODE),
+            BioUtilsRecodeSet::Phred64Encode => recode_table!(PHRED64_SCORE, PHRED64_ENCODE),
+            BioUtilsRecodeSet::Phred64Decode => recode_table!(PHRED64_ENCODE, PHRED64_DECODE),
+            BioUtilsRecodeSet::Solexa64ToPhred33 => recode_table!(SOLEXA_ENCODE, SOLEXA_TO_PHRED33),
+            BioUtilsRecodeSet::Phred64ToPhred33 => recode_table!(PHRED64_ENCODE, PHRED64_TO_PHRED33),
+            BioUtilsRecodeSet::Phred33ToPhred64 => recode_table!(PHRED33_ENCODE, PHRED33_TO_PHRED64),
+            BioUtilsRecodeSet::DnaComplement => recode_table!(DNA, DNA_COMPLEMENT),
+            BioUtilsRecodeSet::DnanComplement => recode_table!(DNAN, DNA_COMPLEMENT),
+            BioUtilsRecodeSet::DnaComplementMixCase => recode_table!(DNA_MIX_CASE, DNA_COMPLEMENT_MIX_CASE),
+            BioUtilsRecodeSet::DnanComplementMixCase => recode_table!(DNAN_MIX_CASE, DNA_COMPLEMENT_MIX_CASE),
+            BioUtilsRecodeSet::Transcribe => recode_table!(DNAN_MIX_CASE, TRANSCRIBE),
+            BioUtilsRecodeSet::ReverseTranscribe => recode_table!(RNAN_MIX_CASE, REVERSE_TRANSCRIBE),
+            BioUtilsRecodeSet::RnaComplement => recode_table!(RNA, RNA_COMPLEMENT),
+            BioUtilsRecodeSet::RnanComplement => recode_table!(RNAN, RNA_COMPLEMENT),
+            BioUtilsRecodeSet::RnaComplementMixCase => recode_table!(RNA_MIX_CASE, RNA_COMPLEMENT_MIX_CASE),
+            BioUtilsRecodeSet::RnanComplementMixCase => recode_table!(RNAN_MIX_CASE, RNA_COMPLEMENT_MIX_CASE),
+            BioUtilsRecodeSet::IupacComplement => recode_table!(IUPAC_NUCLEOTIDE_UPPERCASE, IUPAC_COMPLEMENT_MIX_CASE),
+            BioUtilsRecodeSet::IupacComplementMixCase => recode_table!(IUPAC_NUCLEOTIDE, IUPAC_COMPLEMENT_MIX_CASE),
+            BioUtilsRecodeSet::BisulfiteTop | BioUtilsRecodeSet::BisulfiteTopCpg => recode_table!(DNAN_MIX_CASE, BISULFITE_TOP),
+            BioUtilsRecodeSet::BisulfiteBottom | BioUtilsRecodeSet::BisulfiteBottomCpg => recode_table!(DNAN_MIX_CASE, BISULFITE_BOTTOM),
+        }
+    }
+    /// True if a byte at i of seq keeps its base because of its neighbours, the C or G of a CpG for the CpG-protected bisulfite sets. A lone u8 has no neighbours and is never protected.
+    #[inline]
+    pub fn is_protected(&self, seq: &[u8], i: usize) -> bool {
+        match *self {
+            BioUtilsRecodeSet::BisulfiteTopCpg => seq.get(i + 1).map_or(false, |u| u.eq_ignore_ascii_case(&b'G')),
+            BioUtilsRecodeSet::BisulfiteBottomCpg => i > 0 && seq[i - 1].eq_ignore_ascii_case(&b'C'),
+            _ => false,
+        }
+    }
+    /// True if recoding a byte depends on its neighbours.
+    pub const fn is_context_dependent(&self) -> bool {
+        matches!(*self, BioUtilsRecodeSet::BisulfiteTopCpg | BioUtilsRecodeSet::BisulfiteBottomCpg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tables_match_charsets() {
+        let charsets = [
+            BioUtilsCharSet::Letters, BioUtilsCharSet::LettersUppercase, BioUtilsCharSet::LettersLowercase, BioUtilsCharSet::Nucleotide,
+            BioUtilsCharSet::Dna, BioUtilsCharSet::DnaMixCase, BioUtilsCharSet::Dnan, BioUtilsCharSet::DnanMixCase, BioUtilsCharSet::DnaLowercase,
+            BioUtilsCharSet::Rna, BioUtilsCharSet::RnaMixCase, BioUtilsCharSet::Rnan, BioUtilsCharSet::RnanMixCase, BioUtilsCharSet::RnaLowercase,
+            BioUtilsCharSet::Gap, BioUtilsCharSet::N, BioUtilsCharSet::NMixCase, BioUtilsCharSet::Gc, BioUtilsCharSet::GcMixCase,
+            BioUtilsCharSet::AminoAcid, BioUtilsCharSet::DnaComplement, BioUtilsCharSet::DnaComplementMixCase,
+            BioUtilsCharSet::Phred33Encode, BioUtilsCharSet::Phred33Decode, BioUtilsCharSet::Phred64Encode, BioUtilsCharSet::Phred64Decode,
+            BioUtilsCharSet::Phred33Score, BioUtilsCharSet::Phred64Score, BioUtilsCharSet::SangerEncode, BioUtilsCharSet::SolexaEncode,
+            BioUtilsCharSet::Percent,
+        ];
+        for charset in charsets.iter() {
+            for byte in 0..=255u8 {
+                assert_eq!(charset.table().contains(byte), charset.value().contains(&byte));
+            }
+        }
+        let recodes = [
+            BioUtilsRecodeSet::Phred33Encode, BioUtilsRecodeSet::Phred33Decode, BioUtilsRecodeSet::Phred64Encode, BioUtilsRecodeSet::Phred64Decode,
+            BioUtilsRecodeSet::Solexa64ToPhred33, BioUtilsRecodeSet::Phred64ToPhred33, BioUtilsRecodeSet::Phred33ToPhred64,
+            BioUtilsRecodeSet::DnaComplement, BioUtilsRecodeSet::DnanComplement, BioUtilsRecodeSet::DnaComplementMixCase, BioUtilsRecodeSet::DnanComplementMixCase,
+            BioUtilsRecodeSet::Transcribe, BioUtilsRecodeSet::ReverseTranscribe, BioUtilsRecodeSet::RnaComplement, BioUtilsRecodeSet::RnanComplement,
+            BioUtilsRecodeSet::RnaComplementMixCase, BioUtilsRecodeSet::RnanComplementMixCase,
+            BioUtilsRecodeSet::IupacComplement, BioUtilsRecodeSet::IupacComplementMixCase,
+            BioUtilsRecodeSet::BisulfiteTop, BioUtilsRecodeSet::BisulfiteBottom, BioUtilsRecodeSet::BisulfiteTopCpg, BioUtilsRecodeSet::BisulfiteBottomCpg,
+        ];
+        for code in recodes.iter() {
+            for byte in code.value().charset.iter() {
+                assert!(code.charset_table().contains(*byte));
+                assert_eq!(code.recode_table()[*byte as usize], code.value().recode[*byte as usize]);
+            }
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! NCBI genetic codes (translation tables) as 64-codon amino acid and start strings in NCBI TCAG order, e.g. codon 0 is TTT, 1 TTC, 2 TTA, 3 TTG, 4 TCT.
+//! ```
+//! use bioutils::charsets::codon::GeneticCode;
+//! assert_eq!(GeneticCode::Standard.translate_codon(b"ATG"), b'M');
+//! assert_eq!(GeneticCode::VertebrateMitochondrial.translate_codon(b"TGA"), b'W');
+//! assert_eq!(GeneticCode::Standard.translate_codon(b"TTR"), b'L'); // TTA and TTG are both leucine
+//! assert_eq!(GeneticCode::Standard.translate_codon(b"NNN"), b'X');
+//! assert!(GeneticCode::Bacterial.is_start(b"GTG"));
+//! ```
+
+use super::*;
+
+/// Filler for codons that cannot be translated.
+pub const UNKNOWN_AMINO_ACID: u8 = b'X';
+/// Translation of a stop codon.
+pub const STOP: u8 = b'*';
+
+/// Index of each base in NCBI TCAG order, either case and U as T. COMPLEMENT_FILLER for all others.
+pub const TCAG_INDEX: [u8; 256] = {
+    let mut table = [COMPLEMENT_FILLER; 256];
+    table[b'T' as usize] = 0;
+    table[b't' as usize] = 0;
+    table[b'U' as usize] = 0;
+    table[b'u' as usize] = 0;
+    table[b'C' as usize] = 1;
+    table[b'c' as usize] = 1;
+    table[b'A' as usize] = 2;
+    table[b'a' as usize] = 2;
+    table[b'G' as usize] = 3;
+    table[b'g' as usize] = 3;
+    table
+};
+
+// TCAG index of the bases in nibble bit order A, C, G, T.
+const NIBBLE_TCAG: [usize; 4] = [2, 1, 3, 0];
+
+/// NCBI genetic codes, numbered as the NCBI transl_table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeneticCode {
+    Standard = 1,
+    VertebrateMitochondrial = 2,
+    YeastMitochondrial = 3,
+    MoldMitochondrial = 4,
+    InvertebrateMitochondrial = 5,
+    CiliateNuclear = 6,
+    EchinodermMitochondrial = 9,
+    EuplotidNuclear = 10,
+    Bacterial = 11,
+    AlternativeYeastNuclear = 12,
+    AscidianMitochondrial = 13,
+    AlternativeFlatwormMitochondrial = 14,
+    ChlorophyceanMitochondrial = 16,
+    TrematodeMitochondrial = 21,
+    ScenedesmusObliquusMitochondrial = 22,
+    ThraustochytriumMitochondrial = 23,
+    RhabdopleuridaeMitochondrial = 24,
+    CandidateDivisionSr1 = 25,
+    PachysolenTannophilusNuclear = 26,
+    KaryorelictNuclear = 27,
+    CondylostomaNuclear = 28,
+    MesodiniumNuclear = 29,
+    PeritrichNuclear = 30,
+    BlastocrithidiaNuclear = 31,
+    CephalodiscidaeMitochondrial = 33,
+}
+
+impl GeneticCode {
+    /// Every genetic code, in NCBI id order.
+    pub const ALL: [GeneticCode; 25] = [
+        GeneticCode::Standard, GeneticCode::VertebrateMitochondrial, GeneticCode::YeastMitochondrial, GeneticCode::MoldMitochondrial,
+        GeneticCode::InvertebrateMitochondrial, GeneticCode::CiliateNuclear, GeneticCode::EchinodermMitochondrial, GeneticCode::EuplotidNuclear,
+        GeneticCode::Bacterial, GeneticCode::AlternativeYeastNuclear, GeneticCode::AscidianMitochondrial, GeneticCode::AlternativeFlatwormMitochondrial,
+        GeneticCode::ChlorophyceanMitochondrial, GeneticCode::TrematodeMitochondrial, GeneticCode::ScenedesmusObliquusMitochondrial,
+        GeneticCode::ThraustochytriumMitochondrial, GeneticCode::RhabdopleuridaeMitochondrial, GeneticCode::CandidateDivisionSr1,
+        GeneticCode::PachysolenTannophilusNuclear, GeneticCode::KaryorelictNuclear, GeneticCode::CondylostomaNuclear, GeneticCode::MesodiniumNuclear,
+        GeneticCode::PeritrichNuclear, GeneticCode::BlastocrithidiaNuclear, GeneticCode::CephalodiscidaeMitochondrial,
+    ];
+
+    /// NCBI transl_table id.
+    pub const fn id(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Amino acids of the 64 codons in TCAG order, * for stops.
+    pub const fn amino_acids(&self) -> &'static [u8; 64] {
+        match *self {
+            GeneticCode::Standard => b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::VertebrateMitochondrial => b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSS**VVVVAAAADDEEGGGG",
+            GeneticCode::YeastMitochondrial => b"FFLLSSSSYY**CCWWTTTTPPPPHHQQRRRRIIMMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::MoldMitochondrial => b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::InvertebrateMitochondrial => b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSSSVVVVAAAADDEEGGGG",
+            GeneticCode::CiliateNuclear => b"FFLLSSSSYYQQCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::EchinodermMitochondrial => b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+            GeneticCode::EuplotidNuclear => b"FFLLSSSSYY**CCCWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::Bacterial => b"FFLLSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::AlternativeYeastNuclear => b"FFLLSSSSYY**CC*WLLLSPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::AscidianMitochondrial => b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNKKSSGGVVVVAAAADDEEGGGG",
+            GeneticCode::AlternativeFlatwormMitochondrial => b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+            GeneticCode::ChlorophyceanMitochondrial => b"FFLLSSSSYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::TrematodeMitochondrial => b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIMMTTTTNNNKSSSSVVVVAAAADDEEGGGG",
+            GeneticCode::ScenedesmusObliquusMitochondrial => b"FFLLSS*SYY*LCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::ThraustochytriumMitochondrial => b"FF*LSSSSYY**CC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::RhabdopleuridaeMitochondrial => b"FFLLSSSSYY**CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG",
+            GeneticCode::CandidateDivisionSr1 => b"FFLLSSSSYY**CCGWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::PachysolenTannophilusNuclear => b"FFLLSSSSYY**CC*WLLLAPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::KaryorelictNuclear => b"FFLLSSSSYYQQCCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::CondylostomaNuclear => b"FFLLSSSSYYQQCCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::MesodiniumNuclear => b"FFLLSSSSYYYYCC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::PeritrichNuclear => b"FFLLSSSSYYEECC*WLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::BlastocrithidiaNuclear => b"FFLLSSSSYYEECCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSRRVVVVAAAADDEEGGGG",
+            GeneticCode::CephalodiscidaeMitochondrial => b"FFLLSSSSYYY*CCWWLLLLPPPPHHQQRRRRIIIMTTTTNNKKSSSKVVVVAAAADDEEGGGG",
+        }
+    }
+
+    /// Start codons of the 64 codons in TCAG order, M for starts, * where NCBI marks stops and - for others.
+    pub const fn starts(&self) -> &'static [u8; 64] {
+        match *self {
+            GeneticCode::Standard => b"---M------**--*----M---------------M----------------------------",
+            GeneticCode::VertebrateMitochondrial => b"----------**--------------------MMMM----------**---M------------",
+            GeneticCode::YeastMitochondrial => b"----------**----------------------MM---------------M------------",
+            GeneticCode::MoldMitochondrial => b"--MM------**-------M------------MMMM---------------M------------",
+            GeneticCode::InvertebrateMitochondrial => b"---M------**--------------------MMMM---------------M------------",
+            GeneticCode::CiliateNuclear => b"--------------*--------------------M----------------------------",
+            GeneticCode::EchinodermMitochondrial => b"----------**-----------------------M---------------M------------",
+            GeneticCode::EuplotidNuclear => b"----------**-----------------------M----------------------------",
+            GeneticCode::Bacterial => b"---M------**--*----M------------MMMM---------------M------------",
+            GeneticCode::AlternativeYeastNuclear => b"----------**--*----M---------------M----------------------------",
+            GeneticCode::AscidianMitochondrial => b"---M------**----------------------MM---------------M------------",
+            GeneticCode::AlternativeFlatwormMitochondrial => b"-----------*-----------------------M----------------------------",
+            GeneticCode::ChlorophyceanMitochondrial => b"----------*---*--------------------M----------------------------",
+            GeneticCode::TrematodeMitochondrial => b"----------**-----------------------M---------------M------------",
+            GeneticCode::ScenedesmusObliquusMitochondrial => b"------*---*---*--------------------M----------------------------",
+            GeneticCode::ThraustochytriumMitochondrial => b"--*-------**--*-----------------M--M---------------M------------",
+            GeneticCode::RhabdopleuridaeMitochondrial => b"---M------**-------M---------------M---------------M------------",
+            GeneticCode::CandidateDivisionSr1 => b"---M------**-----------------------M---------------M------------",
+            GeneticCode::PachysolenTannophilusNuclear => b"----------**--*----M---------------M----------------------------",
+            GeneticCode::KaryorelictNuclear => b"--------------*--------------------M----------------------------",
+            GeneticCode::CondylostomaNuclear => b"----------**--*--------------------M----------------------------",
+            GeneticCode::MesodiniumNuclear => b"--------------*--------------------M----------------------------",
+            GeneticCode::PeritrichNuclear => b"--------------*--------------------M----------------------------",
+            GeneticCode::BlastocrithidiaNuclear => b"----------**-----------------------M----------------------------",
+            GeneticCode::CephalodiscidaeMitochondrial => b"---M-------*-------M---------------M---------------M------------",
+        }
+    }
+
+    /// Index of an unambiguous codon in TCAG order, None for ambiguity codes, gaps, other bytes or a length other than 3.
+    #[inline]
+    pub fn codon_index(codon: &[u8]) -> Option<usize> {
+        match codon {
+            [a, b, c] => {
+                let (a, b, c) = (TCAG_INDEX[*a as usize], TCAG_INDEX[*b as usize], TCAG_INDEX[*c as usize]);
+                if (a | b | c) > 3 {
+                    return None
+                }
+                Some(16 * a as usize + 4 * b as usize + c as usize)
+            }
+            _ => None,
+        }
+    }
+
+    // Indices of every unambiguous codon an IUPAC codon stands for, empty if any base is not an IUPAC nucleotide or a gap.
+    fn expand_codon(codon: &[u8]) -> Vec<usize> {
+        let mut indices = vec![0usize];
+        for u in codon {
+            let nibble = match IupacNibble::from_byte(*u) {
+                Some(nibble) if !nibble.is_gap() => nibble,
+                _ => return Vec::new(),
+            };
+            indices = indices.iter().flat_map(|i| (0..4).filter(move |b| nibble.bits() >> b & 1 == 1).map(move |b| 4 * i + NIBBLE_TCAG[b])).collect();
+        }
+        indices
+    }
+
+    /// Translates a codon of either case, T or U. IUPAC ambiguity codes translate to the residue they all agree on, to B (D/N), Z (E/Q) or J (I/L), or X otherwise. --- is a gap.
+    pub fn translate_codon(&self, codon: &[u8]) -> u8 {
+        if let Some(index) = GeneticCode::codon_index(codon) {
+            return self.amino_acids()[index]
+        }
+        if codon.len() == 3 && codon.iter().all(|u| GAP.contains(u)) {
+            return b'-'
+        }
+        let mut residues = GeneticCode::expand_codon(codon).into_iter().map(|i| self.amino_acids()[i]).collect::<Vec<u8>>();
+        if codon.len() != 3 || residues.is_empty() {
+            return UNKNOWN_AMINO_ACID
+        }
+        residues.sort_unstable();
+        residues.dedup();
+        match residues.as_slice() {
+            [residue] => *residue,
+            [b'D', b'N'] => b'B',
+            [b'E', b'Q'] => b'Z',
+            [b'I', b'L'] => b'J',
+            _ => UNKNOWN_AMINO_ACID,
+        }
+    }
+
+    /// Checks if the codon is a start codon of the code. Ambiguous codons must be starts for every codon they stand for.
+    pub fn is_start(&self, codon: &[u8]) -> bool {
+        let indices = GeneticCode::expand_codon(codon);
+        codon.len() == 3 && !indices.is_empty() && indices.iter().all(|i| self.starts()[*i] == b'M')
+    }
+
+    /// Checks if the codon is a stop codon of the code. Ambiguous codons must be stops for every codon they stand for.
+    pub fn is_stop(&self, codon: &[u8]) -> bool {
+        let indices = GeneticCode::expand_codon(codon);
+        codon.len() == 3 && !indices.is_empty() && indices.iter().all(|i| self.amino_acids()[*i] == STOP)
+    }
+}
+
+impl TryFrom<u8> for GeneticCode {
+    type Error = BioUtilsError;
+    /// Genetic code from its NCBI transl_table id.
+    fn try_from(id: u8) -> Result<GeneticCode> {
+        GeneticCode::ALL.iter().find(|code| code.id() == id).copied().ok_or(BioUtilsError::UnknownGeneticCode(id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_matches_codon_hashmap() {
+        for (codon, residue) in CODON_HASHMAP.iter().filter(|(codon, _)| !codon.contains(&b'N')) {
+            assert_eq!(GeneticCode::Standard.translate_codon(codon), *residue);
+        }
+        assert_eq!(GeneticCode::Standard.translate_codon(b"GTN"), b'V');
+        assert_eq!(GeneticCode::Standard.translate_codon(b"RAY"), b'B');
+        assert_eq!(GeneticCode::Standard.translate_codon(b"ATH"), b'I');
+        assert_eq!(GeneticCode::Standard.translate_codon(b"---"), b'-');
+        assert_eq!(GeneticCode::try_from(11).unwrap(), GeneticCode::Bacterial);
+        assert!(GeneticCode::try_from(7).is_err());
+        assert!(GeneticCode::VertebrateMitochondrial.is_stop(b"AGR"));
+        assert!(GeneticCode::ALL.iter().all(|code| GeneticCode::try_from(code.id()).unwrap() == *code));
+    }
+}
@@ -88,9 +88,8 @@ lazy_static! {
 }
 
 lazy_static! {
-    pub static ref NUCLEOTIDE_COMPLEMENT_HASHMAP: HashMap<u8, u8> = vec![
-        (b'A', b'T'), (b'a', b't'), (b'C', b'G'), (b'c', b'g'), (b'G', b'C'), (b'g', b'c'), (b'T', b'A'), (b't', b'a'), (b'U', b'A'), (b'u', b'a'), (b'N', b'N'), (b'n', b'n'), (b'-', b'-'), (b'.', b'.')
-        ].into_iter().collect();
+    pub static ref NUCLEOTIDE_COMPLEMENT_HASHMAP: HashMap<u8, u8> =
+        IUPAC_NUCLEOTIDE.iter().map(|u| (*u, IUPAC_COMPLEMENT_MIX_CASE[*u as usize])).collect();
 }
 
 // Full IUPAC amino acid charset
@@ -155,7 +154,6 @@ pub const DNA_COMPLEMENT: [u8;85] = [
 /// b't' is 103 = a,
 /// b'N' is 78 = N,
 /// b'n' is 110 = n,
-
 pub const DNA_COMPLEMENT_MIX_CASE: [u8;117] = [
     COMPLEMENT_FILLER, COMPLEMENT_FILLER, COMPLEMENT_FILLER, COMPLEMENT_FILLER, COMPLEMENT_FILLER,
     COMPLEMENT_FILLER, COMPLEMENT_FILLER, COMPLEMENT_FILLER, COMPLEMENT_FILLER, COMPLEMENT_FILLER,
@@ -185,6 +183,46 @@ pub const DNA_COMPLEMENT_MIX_CASE: [u8;117] = [
 
 ];
 
+/// RNA complement indexed by byte, A-U, C-G and N-N.
+pub const RNA_COMPLEMENT: [u8; 118] = pair_recode(&[(b'A', b'U'), (b'C', b'G'), (b'G', b'C'), (b'U', b'A'), (b'N', b'N')], false);
+/// RNA complement of either case indexed by byte, case is kept.
+pub const RNA_COMPLEMENT_MIX_CASE: [u8; 118] = pair_recode(&[(b'A', b'U'), (b'C', b'G'), (b'G', b'C'), (b'U', b'A'), (b'N', b'N')], true);
+/// Transcription of DNAN of either case indexed by byte, T to U, case is kept.
+pub const TRANSCRIBE: [u8; 118] = pair_recode(&[(b'A', b'A'), (b'C', b'C'), (b'G', b'G'), (b'T', b'U'), (b'N', b'N')], true);
+/// Reverse transcription of RNAN of either case indexed by byte, U to T, case is kept.
+pub const REVERSE_TRANSCRIBE: [u8; 118] = pair_recode(&[(b'A', b'A'), (b'C', b'C'), (b'G', b'G'), (b'U', b'T'), (b'N', b'N')], true);
+
+// Recode table from uppercase (from, to) pairs, also mapping the lowercase pairs if mix_case. Other bytes are COMPLEMENT_FILLER.
+const fn pair_recode(pairs: &[(u8, u8)], mix_case: bool) -> [u8; 118] {
+    let mut recode = [COMPLEMENT_FILLER; 118];
+    let mut i = 0;
+    while i < pairs.len() {
+        recode[pairs[i].0 as usize] = pairs[i].1;
+        if mix_case {
+            recode[pairs[i].0.to_ascii_lowercase() as usize] = pairs[i].1.to_ascii_lowercase();
+        }
+        i += 1;
+    }
+    recode
+}
+
+/// In-silico bisulfite conversion of the top strand, indexed by byte: C to T and c to t, other DNAN_MIX_CASE bytes unchanged.
+pub const BISULFITE_TOP: [u8; 117] = bisulfite_recode(b'C', b'T');
+/// In-silico bisulfite conversion of the bottom strand as read on the top strand, indexed by byte: G to A and g to a, other DNAN_MIX_CASE bytes unchanged.
+pub const BISULFITE_BOTTOM: [u8; 117] = bisulfite_recode(b'G', b'A');
+
+const fn bisulfite_recode(from: u8, to: u8) -> [u8; 117] {
+    let mut recode = [COMPLEMENT_FILLER; 117];
+    let mut i = 0;
+    while i < DNAN_MIX_CASE.len() {
+        recode[DNAN_MIX_CASE[i] as usize] = DNAN_MIX_CASE[i];
+        i += 1;
+    }
+    recode[from as usize] = to;
+    recode[from.to_ascii_lowercase() as usize] = to.to_ascii_lowercase();
+    recode
+}
+
 lazy_static! {
     pub static ref DNA_HASHSET: HashSet<u8> = new_hashset(&DNA);
 }
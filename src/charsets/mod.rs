@@ -2,6 +2,8 @@ use super::*;
 
 pub mod ascii;
 pub mod iupac;
+pub mod nibble;
+pub mod codon;
 pub mod quality;
 pub mod percent;
 pub mod flags;
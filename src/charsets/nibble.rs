@@ -0,0 +1,172 @@
+//! 4-bit IUPAC nucleotide encoding with one bit per base (A 1, C 2, G 4, T/U 8). Ambiguity codes are the union of their bases, so matching, merging and complementing are bit operations.
+//! ```
+//! use bioutils::charsets::nibble::IupacNibble;
+//! let r = IupacNibble::from_byte(b'R').unwrap();
+//! let a = IupacNibble::from_byte(b'A').unwrap();
+//! let g = IupacNibble::from_byte(b'g').unwrap();
+//! assert!(r.matches(a));
+//! assert_eq!(a.union(g), r);
+//! assert_eq!(r.complement().to_byte(), b'Y');
+//! assert_eq!(r.bases().collect::<Vec<u8>>(), b"AG".to_vec());
+//! ```
+
+use super::*;
+
+pub const NIBBLE_A: u8 = 0b0001;
+pub const NIBBLE_C: u8 = 0b0010;
+pub const NIBBLE_G: u8 = 0b0100;
+pub const NIBBLE_T: u8 = 0b1000;
+
+/// Uppercase DNA IUPAC code of each nibble. 0 is a gap.
+pub const NIBBLE_DECODE: [u8; 16] = [
+    b'-', b'A', b'C', b'M', b'G', b'R', b'S', b'V', b'T', b'W', b'Y', b'H', b'K', b'D', b'B', b'N',
+];
+
+/// Nibble of each IUPAC_NUCLEOTIDE byte (either case, T and U alike), COMPLEMENT_FILLER for all others.
+pub const NIBBLE_ENCODE: [u8; 256] = nibble_encode_table();
+
+const fn nibble_encode_table() -> [u8; 256] {
+    let mut table = [COMPLEMENT_FILLER; 256];
+    let mut nibble = 0;
+    while nibble < 16 {
+        let upper = NIBBLE_DECODE[nibble];
+        table[upper as usize] = nibble as u8;
+        table[upper.to_ascii_lowercase() as usize] = nibble as u8;
+        nibble += 1;
+    }
+    table[b'U' as usize] = NIBBLE_T;
+    table[b'u' as usize] = NIBBLE_T;
+    table[b'.' as usize] = 0;
+    table
+}
+
+/// Uppercase IUPAC nucleotide codes and gaps.
+pub const IUPAC_NUCLEOTIDE_UPPERCASE: [u8; 17] = [
+    b'A', b'C', b'G', b'T', b'U', b'R', b'Y', b'S', b'W', b'K', b'M', b'B', b'D', b'H', b'V', b'N', b'-',
+];
+
+/// Complement of each IUPAC_NUCLEOTIDE byte indexed by byte, computed from the nibbles: R-Y, K-M, B-V, D-H, S, W and N to themselves, U to A. Case and gaps are kept.
+pub const IUPAC_COMPLEMENT_MIX_CASE: [u8; 122] = {
+    let mut recode = [COMPLEMENT_FILLER; 122];
+    let mut i = 0;
+    while i < IUPAC_NUCLEOTIDE.len() {
+        let byte = IUPAC_NUCLEOTIDE[i];
+        recode[byte as usize] = match IupacNibble::from_byte(byte) {
+            Some(nibble) if !nibble.is_gap() && byte.is_ascii_lowercase() => nibble.complement().to_byte().to_ascii_lowercase(),
+            Some(nibble) if !nibble.is_gap() => nibble.complement().to_byte(),
+            _ => byte,
+        };
+        i += 1;
+    }
+    recode
+};
+
+/// IUPAC nucleotide code as a set of bases in the low 4 bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IupacNibble(u8);
+
+impl IupacNibble {
+    /// Encodes an IUPAC_NUCLEOTIDE byte of either case. Gaps are the empty set. None for other bytes.
+    #[inline]
+    pub const fn from_byte(byte: u8) -> Option<IupacNibble> {
+        match NIBBLE_ENCODE[byte as usize] {
+            COMPLEMENT_FILLER => None,
+            nibble => Some(IupacNibble(nibble)),
+        }
+    }
+    /// Nibble from raw bits, only the low 4 bits are kept.
+    #[inline]
+    pub const fn from_bits(bits: u8) -> IupacNibble {
+        IupacNibble(bits & 0b1111)
+    }
+    /// Raw bits.
+    #[inline]
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+    /// Uppercase DNA IUPAC code.
+    #[inline]
+    pub const fn to_byte(self) -> u8 {
+        NIBBLE_DECODE[self.0 as usize]
+    }
+    /// Uppercase RNA IUPAC code, U instead of T.
+    #[inline]
+    pub const fn to_rna_byte(self) -> u8 {
+        match self.0 {
+            NIBBLE_T => b'U',
+            nibble => NIBBLE_DECODE[nibble as usize],
+        }
+    }
+    /// Complement, A<->T and C<->G, which reverses the 4 bits.
+    #[inline]
+    pub const fn complement(self) -> IupacNibble {
+        let n = self.0;
+        IupacNibble(((n & 1) << 3) | ((n & 2) << 1) | ((n & 4) >> 1) | ((n & 8) >> 3))
+    }
+    /// Bases in both codes, e.g. R and Y have none, R and W have A.
+    #[inline]
+    pub const fn intersection(self, other: IupacNibble) -> IupacNibble {
+        IupacNibble(self.0 & other.0)
+    }
+    /// Bases in either code, e.g. A and G merge to R.
+    #[inline]
+    pub const fn union(self, other: IupacNibble) -> IupacNibble {
+        IupacNibble(self.0 | other.0)
+    }
+    /// Checks if the codes share a base, e.g. R matches A and N matches all bases.
+    #[inline]
+    pub const fn matches(self, other: IupacNibble) -> bool {
+        self.0 & other.0 != 0
+    }
+    /// Checks if every base of other is in self, e.g. R contains A but not N.
+    #[inline]
+    pub const fn contains(self, other: IupacNibble) -> bool {
+        self.0 & other.0 == other.0
+    }
+    /// Checks if this is a gap, the empty set.
+    #[inline]
+    pub const fn is_gap(self) -> bool {
+        self.0 == 0
+    }
+    /// Number of bases the code stands for: 1 for ACGT, 2-3 for ambiguity codes, 4 for N, 0 for gaps.
+    #[inline]
+    pub const fn degeneracy(self) -> u32 {
+        self.0.count_ones()
+    }
+    /// Uppercase DNA bases of the code in ACGT order.
+    pub fn bases(self) -> impl Iterator<Item = u8> {
+        (0..4).filter(move |i| self.0 >> i & 1 == 1).map(|i| NIBBLE_DECODE[1 << i])
+    }
+    /// The nth base of the code in ACGT order, e.g. 1 of R is G.
+    pub fn nth_base(self, n: usize) -> Option<u8> {
+        self.bases().nth(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_iupac_equivalents() {
+        let codes: [(u8, &[u8]); 10] = [
+            (b'R', &R_BASES), (b'Y', &Y_BASES), (b'S', &S_BASES), (b'W', &W_BASES), (b'K', &K_BASES),
+            (b'M', &M_BASES), (b'B', &B_BASES), (b'D', &D_BASES), (b'H', &H_BASES), (b'V', &V_BASES),
+        ];
+        for (code, bases) in codes.iter() {
+            let nibble = IupacNibble::from_byte(*code).unwrap();
+            let mut expected = bases.to_vec();
+            expected.sort_by_key(|u| IupacNibble::from_byte(*u).unwrap());
+            assert_eq!(nibble.bases().collect::<Vec<u8>>(), expected);
+            assert_eq!(IupacNibble::from_byte(code.to_ascii_lowercase()), Some(nibble));
+            assert_eq!(nibble.complement().complement(), nibble);
+        }
+        assert!(IUPAC_NUCLEOTIDE.iter().all(|u| IupacNibble::from_byte(*u).is_some()));
+        assert_eq!(IupacNibble::from_byte(b'Q'), None);
+        assert_eq!(IupacNibble::from_byte(b'u').unwrap().to_rna_byte(), b'U');
+        assert_eq!(IupacNibble::from_byte(b'K').unwrap().complement().to_byte(), b'M');
+        for (from, to) in [(b'R', b'Y'), (b'k', b'm'), (b'B', b'V'), (b'd', b'h'), (b'S', b'S'), (b'w', b'w'), (b'N', b'N'), (b'.', b'.'), (b'u', b'a')].iter() {
+            assert_eq!(IUPAC_COMPLEMENT_MIX_CASE[*from as usize], *to);
+        }
+    }
+}
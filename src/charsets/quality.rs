@@ -22,7 +22,7 @@ impl QualityCharSet {
             QualityCharSet::Phred33 => &PHRED33_ENCODE,
             QualityCharSet::Phred64 => &PHRED64_ENCODE,
             QualityCharSet::Solexa => &SOLEXA_ENCODE,
-            QualityCharSet::Sanger => &SOLEXA_ENCODE,
+            QualityCharSet::Sanger => &SANGER_ENCODE,
             QualityCharSet::Phred33Score => &PHRED33_SCORE,
             QualityCharSet::Phred64Score => &PHRED64_SCORE,
             QualityCharSet::SangerScore => &SANGER_SCORE,
@@ -30,6 +30,65 @@ impl QualityCharSet {
     }
 }
 
+/// Encoding of FASTQ quality strings: offset added to the score and the ASCII range it spans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QualityEncoding {
+    /// Phred+33 as written by Illumina 1.8+, ASCII 33-75.
+    Phred33,
+    /// Phred+64 as written by Illumina 1.3-1.7, ASCII 64-126.
+    Phred64,
+    /// Solexa log-odds+64 as written by Solexa/Illumina 1.0, ASCII 59-126.
+    Solexa,
+    /// Phred+33 over the full printable range, e.g. nanopore, ASCII 33-126.
+    Sanger,
+}
+
+impl QualityEncoding {
+    /// ASCII value of score 0.
+    pub const fn offset(&self) -> u8 {
+        match *self {
+            QualityEncoding::Phred33 | QualityEncoding::Sanger => 33,
+            QualityEncoding::Phred64 | QualityEncoding::Solexa => 64,
+        }
+    }
+    /// ASCII range of the encoding.
+    pub const fn range(&self) -> RangeInclusive<usize> {
+        match *self {
+            QualityEncoding::Phred33 => PHRED33_RANGE,
+            QualityEncoding::Phred64 => PHRED64_RANGE,
+            QualityEncoding::Solexa => SOLEXA_MIN..=SOLEXA_MAX,
+            QualityEncoding::Sanger => SANGER_RANGE,
+        }
+    }
+    /// Name of the encoding, e.g. for error messages.
+    pub const fn name(&self) -> &'static str {
+        match *self {
+            QualityEncoding::Phred33 => "Phred33",
+            QualityEncoding::Phred64 => "Phred64",
+            QualityEncoding::Solexa => "Solexa",
+            QualityEncoding::Sanger => "Sanger",
+        }
+    }
+    /// Recode set converting quality strings of this encoding to the other, None if the same or not supported. Scores above the Phred33 maximum are clamped.
+    pub const fn recode_to(&self, to: QualityEncoding) -> Option<BioUtilsRecodeSet> {
+        match (*self, to) {
+            (QualityEncoding::Solexa, QualityEncoding::Phred33) => Some(BioUtilsRecodeSet::Solexa64ToPhred33),
+            (QualityEncoding::Phred64, QualityEncoding::Phred33) => Some(BioUtilsRecodeSet::Phred64ToPhred33),
+            (QualityEncoding::Phred33, QualityEncoding::Phred64) => Some(BioUtilsRecodeSet::Phred33ToPhred64),
+            _ => None,
+        }
+    }
+    /// Quality charset of the encoding.
+    pub const fn charset(&self) -> QualityCharSet {
+        match *self {
+            QualityEncoding::Phred33 => QualityCharSet::Phred33,
+            QualityEncoding::Phred64 => QualityCharSet::Phred64,
+            QualityEncoding::Solexa => QualityCharSet::Solexa,
+            QualityEncoding::Sanger => QualityCharSet::Sanger,
+        }
+    }
+}
+
 /// Phred33 charset: ASCII 33-75
 pub const PHRED33_ENCODE: [u8; 43] = [
     b'!', b'"', b'#', b'$', b'%', b'&', 0x0027, b'(', b')', b'*', b'+', b',', b'-', b'.', b'/',
@@ -40,14 +99,14 @@ pub const PHRED33_ENCODE: [u8; 43] = [
 pub const PHRED33_SCORE: [u8; 43] = [0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,23,24,25,26,27,28,29,30,31,32,33,34,35,36,37,38,39,40,41,42];
 
 /// Phred33 DECODE scores. Array that has filler up til 33, where it starts at 0 for phred33 scores. Should not be used directly, as filler will give wrong information. We use it as "Check the ENCODE is valid phred33 (33-75), and if so get the DECODE from this array"
-pub const PHRED33_DECODE: [u8; 75] = [
+pub const PHRED33_DECODE: [u8; 76] = [
     PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,
     PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,
     PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,
     PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,
     PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,
     PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,
-    PHRED_FILLER,PHRED_FILLER,
+    PHRED_FILLER,PHRED_FILLER,PHRED_FILLER,
     0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,
     16,17,18,19,20,21,22,23,24,25,26,27,28,29,30,
     31,32,33,34,35,36,37,38,39,40,41,42,
@@ -212,6 +271,61 @@ pub const SOLEXA_STR: [&str; 68] = [
     r#"k"#, r#"l"#, r#"m"#, r#"n"#, r#"o"#, r#"p"#, r#"q"#, r#"r"#, r#"s"#, r#"t"#, r#"u"#, r#"v"#,
     r#"w"#, r#"x"#, r#"y"#, r#"z"#, r#"{"#, r#"|"#, r#"}"#, r#"~"#,
 ];
+
+pub const SOLEXA_MIN: usize = 59;
+pub const SOLEXA_MAX: usize = 126;
+/// Phred score of each Solexa score -5 to 62, rounded 10log10(10^(Qs/10) + 1). Solexa scores are log-odds, 10log10(p/(1-p)), so they diverge from Phred below 10.
+pub const SOLEXA_TO_PHRED_SCORE: [u8; 68] = [
+    1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 7, 8, 9, 10, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26,
+    27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54,
+    55, 56, 57, 58, 59, 60, 61, 62,
+];
+
+/// Solexa+64 to Phred+33 indexed by byte, clamped to 'K' (Phred33 score 42).
+pub const SOLEXA_TO_PHRED33: [u8; 127] = {
+    let mut recode = [PHRED_FILLER; 127];
+    let mut i = 0;
+    while i < SOLEXA_TO_PHRED_SCORE.len() {
+        let score = if SOLEXA_TO_PHRED_SCORE[i] > PHRED33_SCORE_MAX_U8 { PHRED33_SCORE_MAX_U8 } else { SOLEXA_TO_PHRED_SCORE[i] };
+        recode[SOLEXA_MIN + i] = score + PHRED33_MIN_U8;
+        i += 1;
+    }
+    recode
+};
+
+/// Phred+64 to Phred+33 indexed by byte, clamped to 'K' (Phred33 score 42).
+pub const PHRED64_TO_PHRED33: [u8; 127] = {
+    let mut recode = [PHRED_FILLER; 127];
+    let mut i = PHRED64_MIN_USIZE;
+    while i <= PHRED64_MAX_USIZE {
+        let score = if PHRED64_DECODE[i] > PHRED33_SCORE_MAX_U8 { PHRED33_SCORE_MAX_U8 } else { PHRED64_DECODE[i] };
+        recode[i] = score + PHRED33_MIN_U8;
+        i += 1;
+    }
+    recode
+};
+
+/// Phred+33 to Phred+64 indexed by byte.
+pub const PHRED33_TO_PHRED64: [u8; 76] = {
+    let mut recode = [PHRED_FILLER; 76];
+    let mut i = PHRED33_MIN_USIZE;
+    while i <= PHRED33_MAX_USIZE {
+        recode[i] = PHRED33_DECODE[i] + PHRED64_MIN_U8;
+        i += 1;
+    }
+    recode
+};
+
+/// Solexa score to Phred score, 10log10(10^(Qs/10) + 1).
+pub fn solexa_to_phred(solexa: f64) -> f64 {
+    10f64 * (10f64.powf(solexa / 10f64) + 1f64).log10()
+}
+
+/// Phred score to Solexa score, 10log10(10^(Qp/10) - 1). Phred 0 has no Solexa score and gives -inf.
+pub fn phred_to_solexa(phred: f64) -> f64 {
+    10f64 * (10f64.powf(phred / 10f64) - 1f64).log10()
+}
+
 lazy_static! {
     /// Solexa/Illumina 1.0 charset: ASCII 59-126.
     pub static ref SOLEXA_HASHSET: HashSet<u8> = new_hashset(&SOLEXA_ENCODE);
@@ -294,6 +408,22 @@ pub const SANGER_RANGE: RangeInclusive<usize> = SANGER_MIN..=SANGER_MAX;
 pub const SANGER_SCORE_RANGE: RangeInclusive<usize> = SANGER_MIN..=SANGER_MAX;
 pub const SANGER_SCORE: [u8; 94] = [0,1,2,3,4,5,6,7,8,9,10,11,12,13,14,15,16,17,18,19,20,21,22,23,24,25,26,27,28,29,30,31,32,33,34,35,36,37,38,39,40,41,42,43,44,45,46,47,48,49,50,51,52,53,54,55,56,57,58,59,60,61,62,63,64,65,66,67,68,69,70,71,72,73,74,75,76,77,78,79,80,81,82,83,84,85,86,87,88,89,90,91,92,93];
 
+/// Error probability of each Phred score 0-93, 10^(-Q/10).
+pub const PHRED_ERROR_PROB: [f64; 94] = [
+    1.0, 0.7943282347242815, 0.6309573444801932, 0.5011872336272722, 0.3981071705534972, 0.31622776601683794, 0.251188643150958, 0.19952623149688797,
+    0.15848931924611134, 0.12589254117941673, 0.1, 0.07943282347242814, 0.06309573444801933, 0.05011872336272722, 0.039810717055349734, 0.03162277660168379,
+    0.025118864315095794, 0.0199526231496888, 0.015848931924611134, 0.012589254117941675, 0.01, 0.007943282347242814, 0.00630957344480193, 0.005011872336272725,
+    0.003981071705534973, 0.0031622776601683794, 0.0025118864315095794, 0.001995262314968879, 0.001584893192461114, 0.0012589254117941675, 0.001, 0.0007943282347242813,
+    0.000630957344480193, 0.0005011872336272725, 0.00039810717055349735, 0.00031622776601683794, 0.00025118864315095795, 0.00019952623149688788, 0.00015848931924611142, 0.00012589254117941674,
+    0.0001, 7.943282347242822e-05, 6.309573444801929e-05, 5.011872336272725e-05, 3.9810717055349695e-05, 3.1622776601683795e-05, 2.5118864315095822e-05, 1.9952623149688786e-05,
+    1.584893192461114e-05, 1.2589254117941661e-05, 1e-05, 7.943282347242822e-06, 6.30957344480193e-06, 5.011872336272725e-06, 3.981071705534969e-06, 3.162277660168379e-06,
+    2.5118864315095823e-06, 1.9952623149688787e-06, 1.584893192461114e-06, 1.2589254117941661e-06, 1e-06, 7.943282347242822e-07, 6.30957344480193e-07, 5.011872336272725e-07,
+    3.981071705534969e-07, 3.162277660168379e-07, 2.5118864315095823e-07, 1.9952623149688787e-07, 1.584893192461114e-07, 1.2589254117941662e-07, 1e-07, 7.943282347242822e-08,
+    6.30957344480193e-08, 5.011872336272725e-08, 3.981071705534969e-08, 3.162277660168379e-08, 2.511886431509582e-08, 1.9952623149688786e-08, 1.5848931924611143e-08, 1.2589254117941661e-08,
+    1e-08, 7.943282347242822e-09, 6.309573444801943e-09, 5.011872336272715e-09, 3.981071705534969e-09, 3.1622776601683795e-09, 2.511886431509582e-09, 1.9952623149688828e-09,
+    1.584893192461111e-09, 1.2589254117941663e-09, 1e-09, 7.943282347242822e-10, 6.309573444801942e-10, 5.011872336272714e-10,
+];
+
 lazy_static! {
     /// Sanger charset as hashset: ASCII 33-126. Used by nanopore (u8-33)
     pub static ref SANGER_HASHSET: HashSet<u8> = new_hashset(&SANGER_ENCODE);
@@ -0,0 +1,193 @@
+//! Error type returned by every fallible function in bioutils. Match on the variants to handle failures instead of crashing.
+//! ```
+//! use bioutils::BioUtilsError;
+//! use bioutils::utils::check::AllAsRefSlice;
+//! use bioutils::charsets::bioutils::*;
+//! match b"ACXT".result_is_all_charset(BioUtilsCharSet::Dna) {
+//!     Err(BioUtilsError::InvalidCharacter { position, byte }) => println!("{} at {}", byte as char, position),
+//!     _ => (),
+//! }
+//! ```
+
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Result with BioUtilsError as the default error.
+pub type Result<T, E = BioUtilsError> = std::result::Result<T, E>;
+
+/// Errors returned by bioutils.
+#[derive(Debug)]
+pub enum BioUtilsError {
+    /// A byte at the position is not in the expected character set.
+    InvalidCharacter { position: usize, byte: u8 },
+    /// None of the bytes are in the expected character set.
+    MissingCharacter,
+    /// A character set to choose from is empty.
+    EmptyCharset,
+    /// A percent is not within 0-100.
+    PercentOutOfRange(u64),
+    /// A percent was calculated with a denominator of zero.
+    ZeroDenominator,
+    /// A quality byte is not within the range of its encoding.
+    QualityOutOfRange { byte: u8, encoding: &'static str },
+    /// No conversion between the quality encodings.
+    UnsupportedQualityConversion { from: &'static str, to: &'static str },
+    /// An integer does not fit in the target type.
+    IntegerOverflow(core::num::TryFromIntError),
+    /// Reading or writing a file failed.
+    Io(io::Error),
+    /// A curl or FTP transfer failed.
+    Network(String),
+    /// A url could not be parsed or joined.
+    InvalidUrl(url::ParseError),
+    /// No file in the remote directory matches the pattern.
+    NoMatchingRemoteFile(String),
+    /// A regex pattern could not be compiled.
+    InvalidPattern(String),
+    /// The file type could not be inferred from the magic bytes.
+    UnknownFileType(PathBuf),
+    /// The file type is known but not supported.
+    UnsupportedFileType { path: PathBuf, mime_type: &'static str },
+    /// The file is gzip but not BGZF, so cannot be indexed.
+    NotBgzf(PathBuf),
+    /// Malformed FASTA, FASTQ, BGZF or index data.
+    InvalidFormat(String),
+    /// Text is not valid UTF-8.
+    InvalidUtf8,
+    /// A region string could not be parsed.
+    InvalidRegion(String),
+    /// The sequence is not in the index.
+    SequenceNotFound(String),
+    /// A writer was used after it was finished.
+    WriterFinished,
+    /// No NCBI genetic code has the id.
+    UnknownGeneticCode(u8),
+    /// A reading frame is not one of 1, 2, 3, -1, -2, -3.
+    InvalidFrame(i8),
+    /// The sequence does not begin with a start codon of the genetic code.
+    NoStartCodon,
+}
+
+impl fmt::Display for BioUtilsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BioUtilsError::InvalidCharacter { position, byte } => write!(f, "Invalid character {:?} at position {}", *byte as char, position),
+            BioUtilsError::MissingCharacter => write!(f, "Slice does not have the charset"),
+            BioUtilsError::EmptyCharset => write!(f, "Cannot choose from an empty charset"),
+            BioUtilsError::PercentOutOfRange(percent) => write!(f, "Percent {} is not within the range 0-100", percent),
+            BioUtilsError::ZeroDenominator => write!(f, "Cannot calculate a percent with a zero denominator"),
+            BioUtilsError::QualityOutOfRange { byte, encoding } => write!(f, "{} is not a valid {} quality", byte, encoding),
+            BioUtilsError::UnsupportedQualityConversion { from, to } => write!(f, "Cannot convert {} qualities to {}", from, to),
+            BioUtilsError::IntegerOverflow(e) => write!(f, "{}", e),
+            BioUtilsError::Io(e) => write!(f, "{}", e),
+            BioUtilsError::Network(message) => write!(f, "Network error: {}", message),
+            BioUtilsError::InvalidUrl(e) => write!(f, "Invalid url: {}", e),
+            BioUtilsError::NoMatchingRemoteFile(pattern) => write!(f, "No remote file matches {}", pattern),
+            BioUtilsError::InvalidPattern(message) => write!(f, "Invalid pattern: {}", message),
+            BioUtilsError::UnknownFileType(path) => write!(f, "File type of {} is not known", path.display()),
+            BioUtilsError::UnsupportedFileType { path, mime_type } => write!(f, "Unsupported file type {} for {}", mime_type, path.display()),
+            BioUtilsError::NotBgzf(path) => write!(f, "{} is gzip but not BGZF and cannot be indexed, recompress it with bgzf::bgzip", path.display()),
+            BioUtilsError::InvalidFormat(message) => write!(f, "{}", message),
+            BioUtilsError::InvalidUtf8 => write!(f, "Text is not valid UTF-8"),
+            BioUtilsError::InvalidRegion(region) => write!(f, "Invalid region {}", region),
+            BioUtilsError::SequenceNotFound(name) => write!(f, "Sequence {} is not in the index", name),
+            BioUtilsError::WriterFinished => write!(f, "Writer is already finished"),
+            BioUtilsError::UnknownGeneticCode(id) => write!(f, "No NCBI genetic code with id {}", id),
+            BioUtilsError::InvalidFrame(frame) => write!(f, "Frame {} is not one of 1, 2, 3, -1, -2, -3", frame),
+            BioUtilsError::NoStartCodon => write!(f, "Sequence does not begin with a start codon"),
+        }
+    }
+}
+
+impl std::error::Error for BioUtilsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BioUtilsError::Io(e) => Some(e),
+            BioUtilsError::InvalidUrl(e) => Some(e),
+            BioUtilsError::IntegerOverflow(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for BioUtilsError {
+    fn from(e: io::Error) -> BioUtilsError {
+        BioUtilsError::Io(e)
+    }
+}
+
+impl From<BioUtilsError> for io::Error {
+    fn from(e: BioUtilsError) -> io::Error {
+        match e {
+            BioUtilsError::Io(e) => e,
+            e => io::Error::new(io::ErrorKind::InvalidData, e),
+        }
+    }
+}
+
+impl From<core::num::TryFromIntError> for BioUtilsError {
+    fn from(e: core::num::TryFromIntError) -> BioUtilsError {
+        BioUtilsError::IntegerOverflow(e)
+    }
+}
+
+impl From<url::ParseError> for BioUtilsError {
+    fn from(e: url::ParseError) -> BioUtilsError {
+        BioUtilsError::InvalidUrl(e)
+    }
+}
+
+impl From<curl::Error> for BioUtilsError {
+    fn from(e: curl::Error) -> BioUtilsError {
+        BioUtilsError::Network(e.to_string())
+    }
+}
+
+impl From<ftp::FtpError> for BioUtilsError {
+    fn from(e: ftp::FtpError) -> BioUtilsError {
+        BioUtilsError::Network(e.to_string())
+    }
+}
+
+impl From<regex::Error> for BioUtilsError {
+    fn from(e: regex::Error) -> BioUtilsError {
+        BioUtilsError::InvalidPattern(e.to_string())
+    }
+}
+
+impl From<std::str::Utf8Error> for BioUtilsError {
+    fn from(_: std::str::Utf8Error) -> BioUtilsError {
+        BioUtilsError::InvalidUtf8
+    }
+}
+
+impl From<std::string::FromUtf8Error> for BioUtilsError {
+    fn from(_: std::string::FromUtf8Error) -> BioUtilsError {
+        BioUtilsError::InvalidUtf8
+    }
+}
+
+impl From<std::num::ParseIntError> for BioUtilsError {
+    fn from(e: std::num::ParseIntError) -> BioUtilsError {
+        BioUtilsError::InvalidFormat(e.to_string())
+    }
+}
+
+impl From<seq_io::fastq::Error> for BioUtilsError {
+    fn from(e: seq_io::fastq::Error) -> BioUtilsError {
+        match e {
+            seq_io::fastq::Error::Io(e) => BioUtilsError::Io(e),
+            e => BioUtilsError::InvalidFormat(e.to_string()),
+        }
+    }
+}
+
+impl From<seq_io::fasta::Error> for BioUtilsError {
+    fn from(e: seq_io::fasta::Error) -> BioUtilsError {
+        match e {
+            seq_io::fasta::Error::Io(e) => BioUtilsError::Io(e),
+            e => BioUtilsError::InvalidFormat(e.to_string()),
+        }
+    }
+}
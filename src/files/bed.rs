@@ -0,0 +1,90 @@
+//! Minimal BED writer for intervals such as CpG islands. Coordinates are 0-based half-open, as in BED.
+//! ```
+//! use bioutils::files::bed::{BedRecord, BedWriter};
+//! let mut writer = BedWriter::new(Vec::new());
+//! writer.write_record(&BedRecord::new("chr1", 10, 250).with_name("CpG:27")).unwrap();
+//! assert_eq!(writer.into_inner(), b"chr1\t10\t250\tCpG:27\n".to_vec());
+//! ```
+
+use super::*;
+use std::fs::File;
+use std::io::Write;
+
+/// BED interval with an optional name and any further columns.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BedRecord {
+    pub chrom: String,
+    pub start: u64,
+    pub end: u64,
+    pub name: Option<String>,
+    pub columns: Vec<String>,
+}
+
+impl BedRecord {
+    /// Creates a BED3 record.
+    pub fn new(chrom: &str, start: u64, end: u64) -> BedRecord {
+        BedRecord { chrom: chrom.to_string(), start, end, name: None, columns: Vec::new() }
+    }
+    /// Sets the name column.
+    pub fn with_name(mut self, name: &str) -> BedRecord {
+        self.name = Some(name.to_string());
+        self
+    }
+    /// Appends a column after the name. Columns are only written if there is a name.
+    pub fn with_column(mut self, column: String) -> BedRecord {
+        self.columns.push(column);
+        self
+    }
+    /// Length of the interval.
+    pub fn len(&self) -> u64 {
+        self.end - self.start
+    }
+    /// True if the interval is empty.
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+}
+
+impl std::fmt::Display for BedRecord {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\t{}\t{}", self.chrom, self.start, self.end)?;
+        if let Some(name) = &self.name {
+            write!(f, "\t{}", name)?;
+            self.columns.iter().try_for_each(|column| write!(f, "\t{}", column))?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes BED records one per line.
+pub struct BedWriter<W: Write> {
+    writer: W,
+}
+
+impl BedWriter<BufWriter<File>> {
+    /// Creates a BED file at the given path.
+    pub fn from_path(path: &Path) -> Result<BedWriter<BufWriter<File>>> {
+        Ok(BedWriter::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W> BedWriter<W> where
+W: Write,
+{
+    /// Creates a new BED writer.
+    pub fn new(writer: W) -> BedWriter<W> {
+        BedWriter { writer }
+    }
+    /// Writes a record.
+    pub fn write_record(&mut self, record: &BedRecord) -> Result<()> {
+        Ok(writeln!(self.writer, "{}", record)?)
+    }
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        Ok(self.writer.flush()?)
+    }
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
@@ -0,0 +1,374 @@
+//! BGZF block compression, the blocked gzip used by samtools, tabix and bgzip.
+//! Each block holds at most 64 KiB of compressed data, so any position in the uncompressed stream can be addressed by a 64-bit virtual offset: the compressed offset of the block start shifted left by 16, or'd with the offset inside the uncompressed block.
+//! ```
+//! use std::io::{Read, Write};
+//! use bioutils::files::bgzf::{BgzfReader, BgzfWriter};
+//!
+//! let mut writer = BgzfWriter::new(Vec::new());
+//! writer.write_all(b">seq1\nACGT\n").unwrap();
+//! let offset = writer.virtual_offset();
+//! writer.write_all(b">seq2\nGGCC\n").unwrap();
+//! let bgzf = writer.finish().unwrap();
+//!
+//! let mut reader = BgzfReader::new(std::io::Cursor::new(bgzf));
+//! reader.seek_virtual(offset).unwrap();
+//! let mut text = String::new();
+//! reader.read_to_string(&mut text).unwrap();
+//! assert_eq!(text, ">seq2\nGGCC\n");
+//! ```
+
+use super::*;
+use std::io::{self, BufRead, Seek, SeekFrom, Write};
+use flate2::{Compression, Crc};
+use flate2::write::DeflateEncoder;
+use flate2::read::DeflateDecoder;
+
+/// Maximum number of uncompressed bytes per block, as used by htslib so a block always fits in 64 KiB after compression.
+pub const BGZF_BLOCK_SIZE: usize = 0xff00;
+/// Maximum size of a compressed block including header and footer.
+pub const BGZF_MAX_BLOCK_SIZE: usize = 0x10000;
+/// Length of a BGZF block header: gzip header with the 6 byte 'BC' extra field.
+pub const BGZF_HEADER_SIZE: usize = 18;
+/// Length of a BGZF block footer: CRC32 and ISIZE.
+pub const BGZF_FOOTER_SIZE: usize = 8;
+/// Empty block marking the end of a BGZF file.
+pub const BGZF_EOF: [u8; 28] = [
+    0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, 0x42, 0x43, 0x02, 0x00,
+    0x1b, 0x00, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+/// Build a virtual offset from the compressed offset of a block and the offset inside its uncompressed data.
+pub fn virtual_offset(block_offset: u64, within_block: u16) -> u64 {
+    (block_offset << 16) | u64::from(within_block)
+}
+
+/// Split a virtual offset into the compressed offset of the block and the offset inside its uncompressed data.
+pub fn split_virtual_offset(virtual_offset: u64) -> (u64, u16) {
+    (virtual_offset >> 16, (virtual_offset & 0xffff) as u16)
+}
+
+/// Compress data into a single BGZF block. The data must not be longer than BGZF_BLOCK_SIZE.
+pub fn compress_block(data: &[u8], level: Compression) -> Result<Vec<u8>> {
+    if data.len() > BGZF_BLOCK_SIZE {
+        return Err(BioUtilsError::InvalidFormat(format!("BGZF block data is {} bytes, the maximum is {}", data.len(), BGZF_BLOCK_SIZE)))
+    }
+    let mut cdata = deflate(data, level)?;
+    if cdata.len() + BGZF_HEADER_SIZE + BGZF_FOOTER_SIZE > BGZF_MAX_BLOCK_SIZE {
+        // Incompressible data, store it instead.
+        cdata = deflate(data, Compression::none())?;
+    }
+    let block_size = cdata.len() + BGZF_HEADER_SIZE + BGZF_FOOTER_SIZE;
+    let mut crc = Crc::new();
+    crc.update(data);
+    let mut block = Vec::with_capacity(block_size);
+    block.extend_from_slice(&[0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, b'B', b'C', 0x02, 0x00]);
+    block.extend_from_slice(&((block_size - 1) as u16).to_le_bytes());
+    block.extend_from_slice(&cdata);
+    block.extend_from_slice(&crc.sum().to_le_bytes());
+    block.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    Ok(block)
+}
+
+fn deflate(data: &[u8], level: Compression) -> Result<Vec<u8>> {
+    let mut encoder = DeflateEncoder::new(Vec::with_capacity(data.len()), level);
+    encoder.write_all(data)?;
+    Ok(encoder.finish()?)
+}
+
+/// Recompress a plain, gzip or BGZF file to BGZF, e.g. to index a gzipped reference downloaded from GENCODE.
+pub fn bgzip(input: &Path, output: &Path) -> Result<()> {
+    let mut reader = open_any(input)?;
+    let mut writer = BgzfWriter::from_path(output)?;
+    io::copy(&mut reader, &mut writer)?;
+    writer.finish()?.flush()?;
+    Ok(())
+}
+
+/// Writes BGZF blocks to the inner writer. Call finish to write the EOF marker; dropping the writer also finishes it but ignores errors.
+pub struct BgzfWriter<W: Write> {
+    writer: Option<W>,
+    buffer: Vec<u8>,
+    level: Compression,
+    block_offset: u64,
+}
+
+impl BgzfWriter<BufWriter<File>> {
+    /// Creates a BGZF file at the given path.
+    pub fn from_path(path: &Path) -> Result<BgzfWriter<BufWriter<File>>> {
+        Ok(BgzfWriter::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W> BgzfWriter<W> where
+W: Write,
+{
+    /// Creates a new BGZF writer with the default compression level.
+    pub fn new(writer: W) -> BgzfWriter<W> {
+        BgzfWriter::with_level(writer, Compression::default())
+    }
+    /// Creates a new BGZF writer with the given compression level.
+    pub fn with_level(writer: W, level: Compression) -> BgzfWriter<W> {
+        BgzfWriter { writer: Some(writer), buffer: Vec::with_capacity(BGZF_BLOCK_SIZE), level, block_offset: 0 }
+    }
+    /// Returns the virtual offset of the next byte written.
+    pub fn virtual_offset(&self) -> u64 {
+        virtual_offset(self.block_offset, self.buffer.len() as u16)
+    }
+    /// Compresses and writes the buffered data as a block, if there is any. The next write starts a new block.
+    pub fn flush_block(&mut self) -> Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(())
+        }
+        let block = compress_block(&self.buffer, self.level)?;
+        match self.writer.as_mut() {
+            Some(writer) => writer.write_all(&block)?,
+            None => return Err(BioUtilsError::WriterFinished),
+        }
+        self.block_offset += block.len() as u64;
+        self.buffer.clear();
+        Ok(())
+    }
+    /// Writes the remaining data and the EOF marker, and returns the inner writer.
+    pub fn finish(mut self) -> Result<W> {
+        self.write_eof()?;
+        match self.writer.take() {
+            Some(writer) => Ok(writer),
+            None => Err(BioUtilsError::WriterFinished),
+        }
+    }
+
+    fn write_eof(&mut self) -> Result<()> {
+        self.flush_block()?;
+        if let Some(writer) = self.writer.as_mut() {
+            writer.write_all(&BGZF_EOF)?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl<W> Write for BgzfWriter<W> where
+W: Write,
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let n = buf.len().min(BGZF_BLOCK_SIZE - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..n]);
+        if self.buffer.len() == BGZF_BLOCK_SIZE {
+            self.flush_block().map_err(io::Error::from)?;
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.flush_block().map_err(io::Error::from)?;
+        match self.writer.as_mut() {
+            Some(writer) => writer.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<W> Drop for BgzfWriter<W> where
+W: Write,
+{
+    fn drop(&mut self) {
+        if self.writer.is_some() {
+            let _ = self.write_eof();
+        }
+    }
+}
+
+/// Reads BGZF blocks from the inner reader and hands out the uncompressed data. Tracks virtual offsets, and can seek to one if the inner reader implements Seek.
+pub struct BgzfReader<R: Read> {
+    reader: R,
+    data: Vec<u8>,
+    position: usize,
+    block_offset: u64,
+    next_block_offset: u64,
+}
+
+impl BgzfReader<BufReader<File>> {
+    /// Opens a BGZF file from the given path.
+    pub fn from_path(path: &Path) -> Result<BgzfReader<BufReader<File>>> {
+        Ok(BgzfReader::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<R> BgzfReader<R> where
+R: Read,
+{
+    /// Creates a new BGZF reader positioned at the start of the inner reader.
+    pub fn new(reader: R) -> BgzfReader<R> {
+        BgzfReader { reader, data: Vec::new(), position: 0, block_offset: 0, next_block_offset: 0 }
+    }
+    /// Returns the virtual offset of the next byte read. Once the current block is consumed this is the start of the next block, as a full block of 65536 bytes has no offset inside it for its end.
+    pub fn virtual_offset(&self) -> u64 {
+        match self.position == self.data.len() {
+            true => virtual_offset(self.next_block_offset, 0),
+            false => virtual_offset(self.block_offset, self.position as u16),
+        }
+    }
+    /// Returns the compressed offset of the current block.
+    pub fn block_offset(&self) -> u64 {
+        self.block_offset
+    }
+    /// Returns the uncompressed data of the current block.
+    pub fn block_data(&self) -> &[u8] {
+        &self.data
+    }
+    /// Reads the next block. Returns false at the end of the input.
+    pub fn read_block(&mut self) -> Result<bool> {
+        self.block_offset = self.next_block_offset;
+        self.position = 0;
+        self.data.clear();
+        let mut header = [0u8; BGZF_HEADER_SIZE];
+        match read_full(&mut self.reader, &mut header)? {
+            0 => return Ok(false),
+            BGZF_HEADER_SIZE => {},
+            _ => return Err(BioUtilsError::InvalidFormat(format!("Truncated BGZF block header at offset {}", self.block_offset))),
+        }
+        if !is_bgzf_header(&header) {
+            return Err(BioUtilsError::InvalidFormat(format!("Invalid BGZF block header at offset {}", self.block_offset)))
+        }
+        let block_size = usize::from(u16::from_le_bytes([header[16], header[17]])) + 1;
+        if block_size < BGZF_HEADER_SIZE + BGZF_FOOTER_SIZE {
+            return Err(BioUtilsError::InvalidFormat(format!("Invalid BGZF block size {} at offset {}", block_size, self.block_offset)))
+        }
+        let mut rest = vec![0u8; block_size - BGZF_HEADER_SIZE];
+        if read_full(&mut self.reader, &mut rest)? != rest.len() {
+            return Err(BioUtilsError::InvalidFormat(format!("Truncated BGZF block at offset {}", self.block_offset)))
+        }
+        let (cdata, footer) = rest.split_at(rest.len() - BGZF_FOOTER_SIZE);
+        let crc32 = u32::from_le_bytes([footer[0], footer[1], footer[2], footer[3]]);
+        let isize = u32::from_le_bytes([footer[4], footer[5], footer[6], footer[7]]) as usize;
+        self.data.reserve(isize);
+        DeflateDecoder::new(cdata).read_to_end(&mut self.data)?;
+        let mut crc = Crc::new();
+        crc.update(&self.data);
+        if self.data.len() != isize || crc.sum() != crc32 {
+            return Err(BioUtilsError::InvalidFormat(format!("Corrupt BGZF block at offset {}", self.block_offset)))
+        }
+        self.next_block_offset = self.block_offset + block_size as u64;
+        Ok(true)
+    }
+}
+
+impl<R> BgzfReader<R> where
+R: Read + Seek,
+{
+    /// Seeks to a virtual offset.
+    pub fn seek_virtual(&mut self, virtual_offset: u64) -> Result<()> {
+        let (block_offset, within_block) = split_virtual_offset(virtual_offset);
+        self.reader.seek(SeekFrom::Start(block_offset))?;
+        self.next_block_offset = block_offset;
+        self.read_block()?;
+        if usize::from(within_block) > self.data.len() {
+            return Err(BioUtilsError::InvalidFormat(format!("Virtual offset {} is past the end of its block", virtual_offset)))
+        }
+        self.position = usize::from(within_block);
+        Ok(())
+    }
+}
+
+impl<R> Read for BgzfReader<R> where
+R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<R> BufRead for BgzfReader<R> where
+R: Read,
+{
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        // Skip empty blocks, such as the EOF marker, until there is data or the input ends.
+        while self.position == self.data.len() {
+            if !self.read_block().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))? {
+                break
+            }
+        }
+        Ok(&self.data[self.position..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.position = (self.position + amt).min(self.data.len());
+    }
+}
+
+/// Read until buf is full or the reader ends, returning the number of bytes read.
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {},
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn round_trip_with_virtual_offsets() {
+        let data = crate::references::phix::PHIX_GENOME.repeat(30);
+        let mut writer = BgzfWriter::new(Vec::new());
+        writer.write_all(&data[..100_000]).unwrap();
+        let offset = writer.virtual_offset();
+        writer.write_all(&data[100_000..]).unwrap();
+        let bgzf = writer.finish().unwrap();
+        assert!(is_bgzf_header(&bgzf));
+        assert!(bgzf.ends_with(&BGZF_EOF));
+
+        let mut decoded = Vec::new();
+        flate2::read::MultiGzDecoder::new(&bgzf[..]).read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, data);
+
+        let mut reader = BgzfReader::new(Cursor::new(&bgzf));
+        reader.seek_virtual(offset).unwrap();
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, &data[100_000..]);
+    }
+
+    #[test]
+    fn virtual_offset_after_full_block() {
+        // htslib allows 65536 bytes in a block, one more than a u16 offset inside it can address
+        let full = vec![b'A'; 0x10000];
+        let cdata = deflate(&full, Compression::default()).unwrap();
+        let mut crc = Crc::new();
+        crc.update(&full);
+        let mut bgzf = vec![0x1f, 0x8b, 0x08, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0x06, 0x00, b'B', b'C', 0x02, 0x00];
+        bgzf.extend_from_slice(&((cdata.len() + BGZF_HEADER_SIZE + BGZF_FOOTER_SIZE - 1) as u16).to_le_bytes());
+        bgzf.extend_from_slice(&cdata);
+        bgzf.extend_from_slice(&crc.sum().to_le_bytes());
+        bgzf.extend_from_slice(&(full.len() as u32).to_le_bytes());
+        let second = bgzf.len() as u64;
+        bgzf.extend_from_slice(&compress_block(b"CGT", Compression::default()).unwrap());
+        bgzf.extend_from_slice(&BGZF_EOF);
+
+        let mut reader = BgzfReader::new(Cursor::new(&bgzf));
+        let mut buf = vec![0u8; 0x10000];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(reader.virtual_offset(), virtual_offset(second, 0));
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte).unwrap();
+        assert_eq!((byte[0], reader.virtual_offset()), (b'C', virtual_offset(second, 1)));
+        let offset = reader.virtual_offset();
+        reader.seek_virtual(offset).unwrap();
+        let mut rest = Vec::new();
+        reader.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b"GT".to_vec());
+        assert_eq!(reader.virtual_offset(), virtual_offset(bgzf.len() as u64, 0));
+    }
+}
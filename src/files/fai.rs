@@ -0,0 +1,386 @@
+//! FASTA index (.fai) for plain and BGZF FASTA, and random-access region fetching without loading the genome.
+//! BGZF FASTA additionally needs a .gzi index mapping uncompressed offsets to blocks, which is built alongside the .fai. Plain gzip cannot be indexed; recompress it with bgzf::bgzip first.
+//! ```
+//! use bioutils::files::fai::*;
+//! use bioutils::utils::find::MemChrAsRef;
+//! // use std::path::Path;
+//! // bioutils::files::bgzf::bgzip(Path::new("GRCh38.primary_assembly.genome.fa.gz"), Path::new("GRCh38.primary_assembly.genome.fa.bgz"));
+//! // index_fasta(Path::new("GRCh38.primary_assembly.genome.fa.bgz"));
+//! // let mut reader = IndexedFastaReader::from_path(Path::new("GRCh38.primary_assembly.genome.fa.bgz")).unwrap();
+//! // let seq = reader.fetch_region("chr1:1000000-1001000").unwrap();
+//! // println!("{:?}", seq.all_positions_cg());
+//! let region = Region::parse("chr1:1,001-2,000").unwrap();
+//! assert_eq!((region.start, region.end), (Some(1000), Some(2000)));
+//! ```
+
+use super::*;
+use std::io::{BufRead, Seek, SeekFrom, Write};
+use bgzf::BgzfReader;
+
+/// One line of a .fai index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FaiRecord {
+    /// Name of the sequence, the header up to the first whitespace.
+    pub name: String,
+    /// Number of bases in the sequence.
+    pub length: u64,
+    /// Uncompressed byte offset of the first base.
+    pub offset: u64,
+    /// Number of bases per line.
+    pub line_bases: u64,
+    /// Number of bytes per line, including the line ending.
+    pub line_width: u64,
+}
+
+impl FaiRecord {
+    /// Uncompressed byte offset of the 0-based position in the sequence.
+    pub fn position_offset(&self, position: u64) -> u64 {
+        if self.line_bases == 0 {
+            return self.offset
+        }
+        self.offset + (position / self.line_bases) * self.line_width + position % self.line_bases
+    }
+}
+
+/// FASTA index, the records of a .fai file in file order.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FastaIndex {
+    pub records: Vec<FaiRecord>,
+}
+
+impl FastaIndex {
+    /// Build the index by scanning uncompressed FASTA. Every line of a sequence except the last must have the same length.
+    pub fn build<R: BufRead>(mut reader: R) -> Result<FastaIndex> {
+        let mut records: Vec<FaiRecord> = Vec::new();
+        let mut line = Vec::new();
+        let mut offset = 0u64;
+        // Set once a sequence line is shorter than the line length (or blank); the next sequence line is then an error.
+        let mut record_ended = false;
+        loop {
+            line.clear();
+            let n = reader.read_until(b'\n', &mut line)? as u64;
+            if n == 0 {
+                break
+            }
+            offset += n;
+            if line[0] == b'>' {
+                let name = line[1..].split(|u| u.is_ascii_whitespace()).next().unwrap_or(&[]);
+                records.push(FaiRecord { name: String::from_utf8(name.to_vec())?, length: 0, offset, line_bases: 0, line_width: 0 });
+                record_ended = false;
+                continue
+            }
+            let bases = line.iter().take_while(|u| **u != b'\n' && **u != b'\r').count() as u64;
+            let record = match records.last_mut() {
+                Some(record) => record,
+                None if bases == 0 => continue,
+                None => return Err(BioUtilsError::InvalidFormat("FASTA sequence found before the first header".to_string())),
+            };
+            if bases == 0 {
+                record_ended = true;
+                continue
+            }
+            if record_ended {
+                return Err(BioUtilsError::InvalidFormat(format!("Different line length in sequence {}", record.name)))
+            }
+            if record.line_bases == 0 {
+                record.line_bases = bases;
+                record.line_width = n;
+            } else if bases > record.line_bases || (n != bases && n - bases != record.line_width - record.line_bases) {
+                return Err(BioUtilsError::InvalidFormat(format!("Different line length in sequence {}", record.name)))
+            }
+            // The last line of the file may have no terminator
+            if bases < record.line_bases || n == bases {
+                record_ended = true;
+            }
+            record.length += bases;
+        }
+        Ok(FastaIndex { records })
+    }
+
+    /// Build the index of a plain or BGZF FASTA file.
+    pub fn build_from_path(path: &Path) -> Result<FastaIndex> {
+        match file_compression(path)? {
+            FileCompression::Plain => FastaIndex::build(BufReader::new(File::open(path)?)),
+            FileCompression::Bgzf => FastaIndex::build(BgzfReader::from_path(path)?),
+            FileCompression::Gzip => Err(BioUtilsError::NotBgzf(path.to_path_buf())),
+        }
+    }
+
+    /// Read a .fai file.
+    pub fn from_path(path: &Path) -> Result<FastaIndex> {
+        let mut records = Vec::new();
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue
+            }
+            let fields = line.split('\t').collect::<Vec<&str>>();
+            if fields.len() < 5 {
+                return Err(BioUtilsError::InvalidFormat(format!("Invalid .fai line: {}", line)))
+            }
+            records.push(FaiRecord {
+                name: fields[0].to_string(),
+                length: fields[1].parse()?,
+                offset: fields[2].parse()?,
+                line_bases: fields[3].parse()?,
+                line_width: fields[4].parse()?,
+            });
+        }
+        Ok(FastaIndex { records })
+    }
+
+    /// Write the index in .fai format.
+    pub fn write<W: Write>(&self, mut writer: W) -> Result<()> {
+        for record in &self.records {
+            writeln!(writer, "{}\t{}\t{}\t{}\t{}", record.name, record.length, record.offset, record.line_bases, record.line_width)?;
+        }
+        Ok(())
+    }
+
+    /// Write the index to a .fai file.
+    pub fn write_to_path(&self, path: &Path) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer)?;
+        Ok(writer.flush()?)
+    }
+
+    /// Returns the record of the named sequence.
+    pub fn get(&self, name: &str) -> Option<&FaiRecord> {
+        self.records.iter().find(|record| record.name == name)
+    }
+}
+
+/// BGZF block index (.gzi): compressed and uncompressed offsets of every block start after the first.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct GziIndex {
+    pub blocks: Vec<(u64, u64)>,
+}
+
+impl GziIndex {
+    /// Build the block index by reading every block of a BGZF stream.
+    pub fn build<R: Read>(reader: R) -> Result<GziIndex> {
+        let mut reader = BgzfReader::new(reader);
+        let mut blocks = Vec::new();
+        let mut uncompressed = 0u64;
+        while reader.read_block()? {
+            if reader.block_offset() != 0 {
+                blocks.push((reader.block_offset(), uncompressed));
+            }
+            uncompressed += reader.block_data().len() as u64;
+        }
+        Ok(GziIndex { blocks })
+    }
+
+    /// Build the block index of a BGZF file.
+    pub fn build_from_path(path: &Path) -> Result<GziIndex> {
+        GziIndex::build(BufReader::new(File::open(path)?))
+    }
+
+    /// Read a .gzi file: the number of entries followed by compressed and uncompressed offset pairs, all little endian u64.
+    pub fn from_path(path: &Path) -> Result<GziIndex> {
+        let bytes = std::fs::read(path)?;
+        let words = bytes.chunks_exact(8).map(|w| u64::from_le_bytes([w[0], w[1], w[2], w[3], w[4], w[5], w[6], w[7]])).collect::<Vec<u64>>();
+        match words.split_first() {
+            Some((n, pairs)) if pairs.len() as u64 == 2 * n => Ok(GziIndex { blocks: pairs.chunks(2).map(|p| (p[0], p[1])).collect() }),
+            _ => Err(BioUtilsError::InvalidFormat(format!("Invalid .gzi file {}", path.display()))),
+        }
+    }
+
+    /// Write the block index to a .gzi file.
+    pub fn write_to_path(&self, path: &Path) -> Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&(self.blocks.len() as u64).to_le_bytes())?;
+        for (compressed, uncompressed) in &self.blocks {
+            writer.write_all(&compressed.to_le_bytes())?;
+            writer.write_all(&uncompressed.to_le_bytes())?;
+        }
+        Ok(writer.flush()?)
+    }
+
+    /// Virtual offset of an uncompressed offset.
+    pub fn virtual_offset(&self, uncompressed_offset: u64) -> u64 {
+        let (compressed, uncompressed) = match self.blocks.partition_point(|(_, u)| *u <= uncompressed_offset) {
+            0 => (0, 0),
+            i => self.blocks[i - 1],
+        };
+        bgzf::virtual_offset(compressed, (uncompressed_offset - uncompressed) as u16)
+    }
+}
+
+/// A region of a sequence, with a 0-based start and exclusive end. Missing bounds mean the start or end of the sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    pub name: String,
+    pub start: Option<u64>,
+    pub end: Option<u64>,
+}
+
+impl Region {
+    /// Parse a samtools style region: "chr", "chr:start" or "chr:start-end", 1-based and inclusive. Commas in the numbers are ignored.
+    pub fn parse(region: &str) -> Result<Region> {
+        if let Some((name, range)) = region.rsplit_once(':') {
+            let range = range.replace(',', "");
+            let (start, end) = match range.split_once('-') {
+                Some((start, end)) => (start.parse::<u64>(), Some(end.parse::<u64>())),
+                None => (range.parse::<u64>(), None),
+            };
+            if let Ok(start) = start {
+                if start == 0 {
+                    return Err(BioUtilsError::InvalidRegion(region.to_string()))
+                }
+                let end = match end {
+                    Some(Ok(end)) if end >= start => Some(end),
+                    Some(_) => return Err(BioUtilsError::InvalidRegion(region.to_string())),
+                    None => None,
+                };
+                return Ok(Region { name: name.to_string(), start: Some(start - 1), end })
+            }
+        }
+        Ok(Region { name: region.to_string(), start: None, end: None })
+    }
+}
+
+enum IndexedSource {
+    Plain(BufReader<File>),
+    Bgzf(BgzfReader<BufReader<File>>, GziIndex),
+}
+
+/// Random-access FASTA reader backed by a .fai index (and .gzi for BGZF).
+pub struct IndexedFastaReader {
+    index: FastaIndex,
+    source: IndexedSource,
+}
+
+impl IndexedFastaReader {
+    /// Opens a plain or BGZF FASTA, using the .fai and .gzi next to it if present or building them in memory otherwise.
+    pub fn from_path(path: &Path) -> Result<IndexedFastaReader> {
+        let fai_path = index_path(path, "fai");
+        let index = match fai_path.exists() {
+            true => FastaIndex::from_path(&fai_path)?,
+            false => FastaIndex::build_from_path(path)?,
+        };
+        IndexedFastaReader::with_index(path, index)
+    }
+
+    /// Opens a plain or BGZF FASTA with the given index.
+    pub fn with_index(path: &Path, index: FastaIndex) -> Result<IndexedFastaReader> {
+        let source = match file_compression(path)? {
+            FileCompression::Plain => IndexedSource::Plain(BufReader::new(File::open(path)?)),
+            FileCompression::Bgzf => {
+                let gzi_path = index_path(path, "gzi");
+                let gzi = match gzi_path.exists() {
+                    true => GziIndex::from_path(&gzi_path)?,
+                    false => GziIndex::build_from_path(path)?,
+                };
+                IndexedSource::Bgzf(BgzfReader::from_path(path)?, gzi)
+            },
+            FileCompression::Gzip => return Err(BioUtilsError::NotBgzf(path.to_path_buf())),
+        };
+        Ok(IndexedFastaReader { index, source })
+    }
+
+    /// Returns the index.
+    pub fn index(&self) -> &FastaIndex {
+        &self.index
+    }
+
+    /// Fetch a samtools style region, e.g. "chr1:1000-2000" (1-based, inclusive).
+    pub fn fetch_region(&mut self, region: &str) -> Result<Vec<u8>> {
+        let region = Region::parse(region)?;
+        let length = match self.index.get(&region.name) {
+            Some(record) => record.length,
+            None => return Err(BioUtilsError::SequenceNotFound(region.name.to_string())),
+        };
+        self.fetch(&region.name, region.start.unwrap_or(0), region.end.unwrap_or(length))
+    }
+
+    /// Fetch the bases of the named sequence from the 0-based start up to the exclusive end. The end is clamped to the sequence length.
+    pub fn fetch(&mut self, name: &str, start: u64, end: u64) -> Result<Vec<u8>> {
+        let record = match self.index.get(name) {
+            Some(record) => record.clone(),
+            None => return Err(BioUtilsError::SequenceNotFound(name.to_string())),
+        };
+        let end = end.min(record.length);
+        if start >= end {
+            return Ok(Vec::new())
+        }
+        let start_offset = record.position_offset(start);
+        let end_offset = record.position_offset(end - 1) + 1;
+        let mut bytes = vec![0u8; (end_offset - start_offset) as usize];
+        match &mut self.source {
+            IndexedSource::Plain(reader) => {
+                reader.seek(SeekFrom::Start(start_offset))?;
+                reader.read_exact(&mut bytes)?;
+            },
+            IndexedSource::Bgzf(reader, gzi) => {
+                reader.seek_virtual(gzi.virtual_offset(start_offset))?;
+                reader.read_exact(&mut bytes)?;
+            },
+        }
+        bytes.retain(|u| *u != b'\n' && *u != b'\r');
+        Ok(bytes)
+    }
+}
+
+/// Path of an index next to the FASTA, e.g. genome.fa.fai.
+pub fn index_path(path: &Path, extension: &str) -> std::path::PathBuf {
+    let mut index = path.as_os_str().to_owned();
+    index.push(".");
+    index.push(extension);
+    std::path::PathBuf::from(index)
+}
+
+/// Build and write the .fai (and .gzi for BGZF) next to a plain or BGZF FASTA file.
+pub fn index_fasta(path: &Path) -> Result<FastaIndex> {
+    let index = FastaIndex::build_from_path(path)?;
+    index.write_to_path(&index_path(path, "fai"))?;
+    if file_compression(path)? == FileCompression::Bgzf {
+        GziIndex::build_from_path(path)?.write_to_path(&index_path(path, "gzi"))?;
+    }
+    Ok(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::files::fasta::FastaWriter;
+    use crate::references::phix::PHIX_GENOME;
+
+    #[test]
+    fn fetches_from_plain_and_bgzf() {
+        let dir = std::env::temp_dir().join(format!("bioutils_{}_fetches_from_plain_and_bgzf", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let plain = dir.join("fai.fa");
+        let bgz = dir.join("fai.fa.bgz");
+        let mut fasta = FastaWriter::with_line_width(Vec::new(), 60);
+        fasta.write(b"first desc", b"ACGTACGTAC").unwrap();
+        for _ in 0..20 {
+            fasta.write(b"phix", &PHIX_GENOME).unwrap();
+        }
+        let fasta = fasta.into_inner();
+        std::fs::write(&plain, &fasta).unwrap();
+        let mut writer = bgzf::BgzfWriter::from_path(&bgz).unwrap();
+        writer.write_all(&fasta).unwrap();
+        writer.finish().unwrap();
+
+        for path in [&plain, &bgz].iter() {
+            let index = index_fasta(path).unwrap();
+            assert_eq!(index.records[0], FaiRecord { name: "first".to_string(), length: 10, offset: 12, line_bases: 10, line_width: 11 });
+            let mut reader = IndexedFastaReader::from_path(path).unwrap();
+            assert_eq!(reader.fetch_region("first:2-4").unwrap(), b"CGT");
+            assert_eq!(reader.fetch("phix", 55, 200).unwrap(), &PHIX_GENOME[55..200]);
+            assert_eq!(reader.fetch_region("phix").unwrap(), &PHIX_GENOME[..]);
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn indexes_without_trailing_newline() {
+        let index = FastaIndex::build(&b">a\nACGT\nACGT"[..]).unwrap();
+        assert_eq!(index.records, FastaIndex::build(&b">a\nACGT\nACGT\n"[..]).unwrap().records);
+        assert_eq!(index.records[0], FaiRecord { name: "a".to_string(), length: 8, offset: 3, line_bases: 4, line_width: 5 });
+        let index = FastaIndex::build(&b">a\nACGT\nAC\n>b\nACG"[..]).unwrap();
+        assert_eq!((index.records[0].length, index.records[1].length), (6, 3));
+        assert!(FastaIndex::build(&b">a\nACGT\nACGTA"[..]).is_err());
+    }
+}
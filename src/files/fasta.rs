@@ -0,0 +1,161 @@
+//! Multi-record FASTA reader and writer. Plain, gzip and BGZF files are opened with open_any, and sequences are handed out as owned u8 vectors so they can be recoded in place.
+//! ```
+//! use bioutils::files::fasta::{FastaReader, FastaWriter};
+//! use bioutils::utils::recode::BioUtilsAsMutDoubleEndedIterator;
+//! use bioutils::charsets::bioutils::*;
+//!
+//! let fasta = b">seq1 first\nACGT\nAC\n>seq2\nGGCC\n";
+//! let reader = FastaReader::new(&fasta[..]);
+//! let mut writer = FastaWriter::with_line_width(Vec::new(), 3);
+//! for record in reader {
+//!     let mut record = record.unwrap();
+//!     // Reverse complement the sequence in place
+//!     record.seq.mut_rev_recode(BioUtilsRecodeSet::DnaComplement);
+//!     writer.write_record(&record).unwrap();
+//! }
+//! println!("{}", String::from_utf8(writer.into_inner()).unwrap());
+//! ```
+
+use super::*;
+use std::fs::File;
+use std::io::{BufRead, Write};
+use seq_io::fasta::{Reader, Record};
+
+/// Default number of bases per line when writing FASTA.
+pub const FASTA_LINE_WIDTH: usize = 60;
+
+/// Multi-record FASTA reader. Sequences spread over multiple lines are joined into a single u8 vector.
+pub struct FastaReader<R: Read> {
+    reader: Reader<R>,
+}
+
+impl FastaReader<Box<dyn BufRead>> {
+    /// Opens a plain, gzip or BGZF FASTA file from the given path.
+    pub fn from_path(path: &Path) -> Result<FastaReader<Box<dyn BufRead>>> {
+        Ok(FastaReader::new(open_any(path)?))
+    }
+}
+
+impl<R> FastaReader<R> where
+R: Read,
+{
+    /// Creates a new FASTA reader from anything that implements Read.
+    pub fn new(reader: R) -> FastaReader<R> {
+        FastaReader { reader: Reader::new(reader) }
+    }
+}
+
+impl<R> Iterator for FastaReader<R> where
+R: Read,
+{
+    type Item = Result<FastaRecord>;
+
+    fn next(&mut self) -> Option<Result<FastaRecord>> {
+        match self.reader.next()? {
+            Ok(record) => Some(Ok(FastaRecord { head: record.head().to_vec(), seq: record.owned_seq() })),
+            Err(e) => Some(Err(e.into())),
+        }
+    }
+}
+
+/// A FASTA record with the header (without '>') and the full sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastaRecord {
+    pub head: Vec<u8>,
+    pub seq: Vec<u8>,
+}
+
+impl FastaRecord {
+    /// Creates a new record from a header and a sequence.
+    pub fn new(head: &[u8], seq: &[u8]) -> FastaRecord {
+        FastaRecord { head: head.to_vec(), seq: seq.to_vec() }
+    }
+    /// Returns the id of the record, everything in the header before the first space.
+    pub fn id(&self) -> Result<&str> {
+        Ok(std::str::from_utf8(self.id_bytes())?)
+    }
+    /// Returns the id of the record as bytes.
+    pub fn id_bytes(&self) -> &[u8] {
+        self.head.split(|u| *u == b' ').next().unwrap_or(&[])
+    }
+    /// Returns the sequence.
+    pub fn seq(&self) -> &[u8] {
+        &self.seq
+    }
+    /// Returns the sequence as mutable, e.g. for mut_rev_recode or mut_xna_clean.
+    pub fn seq_mut(&mut self) -> &mut [u8] {
+        &mut self.seq
+    }
+}
+
+/// FASTA writer that wraps sequence lines at a configurable width. A width of 0 writes each sequence on a single line.
+pub struct FastaWriter<W: Write> {
+    writer: W,
+    line_width: usize,
+}
+
+impl FastaWriter<BufWriter<File>> {
+    /// Creates a FASTA file at the given path, wrapping lines at 60 bases.
+    pub fn from_path(path: &Path) -> Result<FastaWriter<BufWriter<File>>> {
+        Ok(FastaWriter::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W> FastaWriter<W> where
+W: Write,
+{
+    /// Creates a new FASTA writer wrapping lines at 60 bases.
+    pub fn new(writer: W) -> FastaWriter<W> {
+        FastaWriter::with_line_width(writer, FASTA_LINE_WIDTH)
+    }
+    /// Creates a new FASTA writer wrapping lines at the given width. A width of 0 disables wrapping.
+    pub fn with_line_width(writer: W, line_width: usize) -> FastaWriter<W> {
+        FastaWriter { writer, line_width }
+    }
+    /// Writes a header (without '>') and a sequence.
+    pub fn write(&mut self, head: &[u8], seq: &[u8]) -> Result<()> {
+        self.writer.write_all(b">")?;
+        self.writer.write_all(head)?;
+        self.writer.write_all(b"\n")?;
+        if self.line_width == 0 {
+            self.writer.write_all(seq)?;
+            self.writer.write_all(b"\n")?;
+        } else {
+            for line in seq.chunks(self.line_width) {
+                self.writer.write_all(line)?;
+                self.writer.write_all(b"\n")?;
+            }
+        }
+        Ok(())
+    }
+    /// Writes a FASTA record.
+    pub fn write_record(&mut self, record: &FastaRecord) -> Result<()> {
+        self.write(&record.head, &record.seq)
+    }
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        Ok(self.writer.flush()?)
+    }
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let fasta = b">seq1 first\nACGTA\nC\n>seq2\n\n>seq3\nGG\n";
+        let records = FastaReader::new(&fasta[..]).collect::<Result<Vec<FastaRecord>>>().unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].id().unwrap(), "seq1");
+        assert_eq!(records[0].seq(), b"ACGTAC");
+        assert_eq!(records[1].seq(), b"");
+        let mut writer = FastaWriter::with_line_width(Vec::new(), 4);
+        records.iter().for_each(|record| writer.write_record(record).unwrap());
+        assert_eq!(writer.into_inner(), b">seq1 first\nACGT\nAC\n>seq2\n>seq3\nGG\n".to_vec());
+    }
+}
@@ -0,0 +1,187 @@
+//! Streaming FASTQ reader built on seq_io. Records borrow from the reader's buffer, so the sequence and quality slices go straight into the bioutils traits without copying.
+//! ```
+//! use bioutils::files::fastq::FastqReader;
+//! use bioutils::utils::check::AllAsRefSlice;
+//! use bioutils::utils::count::PercentAsRefOrdSlice;
+//! use bioutils::utils::recode::BioUtilsRecodeAsRefSlice;
+//! use bioutils::charsets::bioutils::*;
+//!
+//! let fastq = b"@read1\nACGT\n+\nIIII\n@read2\nACGN\n+\nII##\n";
+//! let mut reader = FastqReader::new(&fastq[..]);
+//! while let Some(record) = reader.next() {
+//!     let record = record.unwrap();
+//!     // Check the sequence against a charset
+//!     println!("{:?}", record.seq().is_all_charset(BioUtilsCharSet::Dna));
+//!     // Decode the qualities to phred33 scores
+//!     let scores = record.qual().recode(BioUtilsRecodeSet::Phred33Decode).unwrap();
+//!     // Percent of bases with a phred33 score of 30 or more
+//!     println!("{:?}", scores.percent_ge(&30u8).unwrap());
+//! }
+//! ```
+
+use super::*;
+use std::io::{BufRead, Write};
+use std::ops::Range;
+use seq_io::fastq::{Reader, RefRecord, OwnedRecord, Record};
+use crate::utils::quality::{QualityDetection, QualityDetector};
+
+/// Detects the quality encoding of a plain, gzip or BGZF FASTQ file from its first n records.
+pub fn detect_quality_encoding(path: &Path, n: usize) -> Result<QualityDetection> {
+    FastqReader::from_path(path)?.detect_quality_encoding(n)
+}
+
+/// Streaming FASTQ reader. Records are borrowed from the internal buffer and are only valid until the next call to `next`.
+pub struct FastqReader<R: Read> {
+    reader: Reader<R>,
+}
+
+impl FastqReader<Box<dyn BufRead>> {
+    /// Opens a plain, gzip or BGZF FASTQ file from the given path.
+    pub fn from_path(path: &Path) -> Result<FastqReader<Box<dyn BufRead>>> {
+        Ok(FastqReader::new(open_any(path)?))
+    }
+}
+
+impl<R> FastqReader<R> where
+R: Read,
+{
+    /// Creates a new FASTQ reader from anything that implements Read.
+    pub fn new(reader: R) -> FastqReader<R> {
+        FastqReader { reader: Reader::new(reader) }
+    }
+
+    /// Returns the next record borrowed from the reader, or None at the end of the input.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<Result<FastqRecord<'_>>> {
+        match self.reader.next()? {
+            Ok(record) => Some(Ok(FastqRecord { record })),
+            Err(e) => Some(Err(e.into())),
+        }
+    }
+
+    /// Reads up to n records and detects their quality encoding. The records are consumed, so open the input again to process it.
+    pub fn detect_quality_encoding(&mut self, n: usize) -> Result<QualityDetection> {
+        let mut detector = QualityDetector::new();
+        while detector.records() < n {
+            match self.next() {
+                Some(record) => detector.add(record?.qual()),
+                None => break,
+            }
+        }
+        detector.detect()
+    }
+
+    /// Reads every remaining record into owned records. Only use on inputs that fit in memory.
+    pub fn to_owned_records(&mut self) -> Result<Vec<FastqOwnedRecord>> {
+        let mut records = Vec::new();
+        while let Some(record) = self.next() {
+            records.push(record?.to_owned_record());
+        }
+        Ok(records)
+    }
+}
+
+/// A FASTQ record that borrows the header, sequence and quality from the reader's buffer.
+pub struct FastqRecord<'a> {
+    record: RefRecord<'a>,
+}
+
+impl<'a> FastqRecord<'a> {
+    /// Returns the header line without the leading '@'.
+    pub fn head(&self) -> &[u8] {
+        self.record.head()
+    }
+    /// Returns the id of the record, everything in the header before the first space.
+    pub fn id(&self) -> Result<&str> {
+        Ok(self.record.id()?)
+    }
+    /// Returns the sequence line.
+    pub fn seq(&self) -> &[u8] {
+        self.record.seq()
+    }
+    /// Returns the quality line.
+    pub fn qual(&self) -> &[u8] {
+        self.record.qual()
+    }
+    /// Checks if the sequence and quality lines are the same length.
+    pub fn is_length_eq(&self) -> bool {
+        self.seq().len() == self.qual().len()
+    }
+    /// Copies the record into an owned record, e.g. to modify the sequence in place.
+    pub fn to_owned_record(&self) -> FastqOwnedRecord {
+        FastqOwnedRecord::from(self.record.to_owned_record())
+    }
+    /// Writes the record to the writer in FASTQ format.
+    pub fn write<W: Write>(&self, writer: W) -> Result<()> {
+        Ok(self.record.write(writer)?)
+    }
+}
+
+/// A FASTQ record that owns its data. Sequence and quality may be modified in place, e.g. with mut_recode or mut_rev_recode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastqOwnedRecord {
+    pub head: Vec<u8>,
+    pub seq: Vec<u8>,
+    pub qual: Vec<u8>,
+}
+
+impl FastqOwnedRecord {
+    /// Keeps only the range of the sequence and quality, e.g. as returned by the trimmers in utils::trim.
+    pub fn cut(&mut self, range: Range<usize>) {
+        self.seq.truncate(range.end);
+        self.seq.drain(..range.start.min(self.seq.len()));
+        self.qual.truncate(range.end);
+        self.qual.drain(..range.start.min(self.qual.len()));
+    }
+    /// Writes the record to the writer in FASTQ format.
+    pub fn write<W: Write>(&self, writer: W) -> Result<()> {
+        Ok(seq_io::fastq::write_to(writer, &self.head, &self.seq, &self.qual)?)
+    }
+}
+
+impl From<OwnedRecord> for FastqOwnedRecord {
+    fn from(record: OwnedRecord) -> FastqOwnedRecord {
+        FastqOwnedRecord { head: record.head, seq: record.seq, qual: record.qual }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::check::AllAsRefSlice;
+    use crate::utils::recode::BioUtilsRecodeAsRefSlice;
+    use crate::utils::trim::{TrimAsRefSlice, TrimStep};
+
+    #[test]
+    fn reads_records() {
+        let fastq = b"@read1 desc\nACGT\n+\nIIII\n@read2\nACGN\n+\nII##\n";
+        let mut reader = FastqReader::new(&fastq[..]);
+        let record = reader.next().unwrap().unwrap();
+        assert_eq!(record.id().unwrap(), "read1");
+        assert!(record.seq().is_all_charset(BioUtilsCharSet::Dna));
+        assert!(record.is_length_eq());
+        let record = reader.next().unwrap().unwrap();
+        assert!(!record.seq().is_all_charset(BioUtilsCharSet::Dna));
+        assert_eq!(record.qual(), b"II##");
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn detects_quality_encoding() {
+        let fastq = b"@read1\nACGT\n+\nhhhh\n@read2\nACGN\n+\nhhhB\n@read3\nACGN\n+\nII##\n";
+        let detection = FastqReader::new(&fastq[..]).detect_quality_encoding(2).unwrap();
+        assert_eq!(detection.encoding, QualityEncoding::Phred64);
+        assert_eq!(detection.records, 2);
+        let detection = FastqReader::new(&fastq[..]).detect_quality_encoding(100).unwrap();
+        assert_eq!(detection.encoding, QualityEncoding::Sanger);
+        assert_eq!(detection.records, 3);
+    }
+
+    #[test]
+    fn cuts_trimmed_range() {
+        let mut record = FastqOwnedRecord { head: b"read1".to_vec(), seq: b"ACGTACGT".to_vec(), qual: b"#IIIII##".to_vec() };
+        let scores = record.qual.recode(BioUtilsRecodeSet::Phred33Decode).unwrap();
+        record.cut(scores.trim(&[TrimStep::Leading(3), TrimStep::Trailing(3)]).unwrap().unwrap());
+        assert_eq!((record.seq, record.qual), (b"CGTAC".to_vec(), b"IIIII".to_vec()));
+    }
+}
@@ -9,59 +9,61 @@
 //! // bioutils_curl(fastq_ftp, fastq_gz, &out_directory);    
 //! ```
 
-use std::path::Path;
+use super::*;
 use std::io::{Write};
-use std::fs::File;
 use curl::easy::Easy;
-use url::{Url, ParseError};
+use url::Url;
 
 // pub const VERSION: &str = "v37";
 // pub const BASE_URL: &str = "ftp://ftp.ebi.ac.uk/pub/databases/gencode/Gencode_human/latest_release/";
 
 /// Function to intake a base url and a filename and paste them together to generate a url where the file may be found.
-pub fn build_url(url: &str, filename: &str) -> Result<Url, ParseError> {
-    let base = Url::parse(url).expect("Base url cannot be parsed");
+pub fn build_url(url: &str, filename: &str) -> Result<Url> {
+    let base = Url::parse(url)?;
     let joined = base.join(filename)?;
     Ok(joined)
 }
 
 /// Download a file from a base url.
-pub fn curl_url(url: &str, output_directory: &std::path::Path) {
-    // Generate url to get file
-    let mut easy = Easy::new();
-    let file_url = url.to_string();
-    easy.url(&file_url).unwrap();
-    let file_name = Path::new(&file_url).file_name().unwrap();
-    // Create file on system to write on
-    let mut file = match File::create(&output_directory.join(file_name)) {
-        Err(why) => panic!("couldn't create {}", why),
-        Ok(file) => file,
+pub fn curl_url(url: &str, output_directory: &std::path::Path) -> Result<()> {
+    let file_name = match Path::new(url).file_name() {
+        Some(file_name) => file_name,
+        None => return Err(BioUtilsError::Network(format!("No file name in url {}", url))),
     };
-    // Write to file on system
-    easy.write_function(move |data| {
-        file.write_all(data).unwrap();
-        Ok(data.len())
-    }).unwrap();
-    easy.perform().unwrap();
-    // println!("{}", easy.response_code().unwrap());
+    curl_to_file(url, &output_directory.join(file_name))
 }
 
 /// Download a file from a base url and a filename.
-pub fn bioutils_curl(url: &str, filename: &str, output_directory: &std::path::Path) {
+pub fn bioutils_curl(url: &str, filename: &str, output_directory: &std::path::Path) -> Result<()> {
+    let file_url = build_url(url, filename)?.to_string();
+    curl_to_file(&file_url, &output_directory.join(filename))
+}
+
+/// Download the url into the file at the given path. Write errors abort the transfer and are returned rather than the curl error.
+pub fn curl_to_file(url: &str, path: &Path) -> Result<()> {
     // Generate url to get file
     let mut easy = Easy::new();
-    let file_url = build_url(url, filename).expect("Cannot build file url").to_string();
-    easy.url(&file_url).unwrap();
+    easy.url(url)?;
     // Create file on system to write on
-    let mut file = match File::create(&output_directory.join(filename)) {
-        Err(why) => panic!("couldn't create {}", why),
-        Ok(file) => file,
+    let mut file = File::create(path)?;
+    let mut write_error = None;
+    let performed = {
+        // Write to file on system
+        let mut transfer = easy.transfer();
+        transfer.write_function(|data| match file.write_all(data) {
+            Ok(()) => Ok(data.len()),
+            // Returning a short count makes curl abort the transfer.
+            Err(e) => {
+                write_error = Some(e);
+                Ok(0)
+            },
+        })?;
+        transfer.perform()
     };
-    // Write to file on system
-    easy.write_function(move |data| {
-        file.write_all(data).expect("Cannot write to file");
-        Ok(data.len())
-    }).unwrap();
-    easy.perform().expect("Cannot write to file");
+    if let Some(e) = write_error {
+        return Err(BioUtilsError::Io(e))
+    }
+    performed?;
     // println!("{}", easy.response_code().unwrap());
-}
\ No newline at end of file
+    Ok(())
+}
@@ -1,19 +1,131 @@
+//! Reading, writing and downloading sequence files.
+//! open_any sniffs the magic bytes of a file and returns a buffered reader that transparently decompresses gzip and BGZF.
+//! ```
+//! use std::io::BufRead;
+//! use bioutils::files::open_any;
+//! // let reader = open_any(std::path::Path::new("reads.fastq.gz")).unwrap();
+//! // for line in reader.lines() {
+//! //     println!("{}", line.unwrap());
+//! // }
+//! ```
+
 use super::*;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use flate2::read::MultiGzDecoder;
 
 pub mod http;
+pub mod fastq;
+pub mod fasta;
+pub mod bgzf;
+pub mod fai;
+pub mod bed;
+
+/// gzip magic bytes, shared by BGZF.
+pub const GZ_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Compression of a file as detected from its magic bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileCompression {
+    Plain,
+    Gzip,
+    Bgzf,
+}
 
-/// Check whether is a gz file type with the Infer crate and return a boolean
-pub fn is_gz(path: &Path) -> bool {
-    let kind = infer_kind(path);
-    kind.extension() == "gz"
+/// Check whether is a gz file type with the Infer crate and return a boolean. Files without a known type (e.g. plain text) are not gz.
+pub fn is_gz(path: &Path) -> Result<bool> {
+    match infer::get_from_path(path)? {
+        Some(kind) => Ok(kind.extension() == "gz"),
+        None => Ok(false),
+    }
 }
 
-/// Use the Infer crate to get the mime type and extension
-pub fn infer_kind(path: &Path) -> infer::Type {
-    
-    infer::get_from_path(path)
-    .expect("file read successfully")
-    .expect("file type is known")
+/// Check whether the file is BGZF, a gzip file whose first member header carries the 'BC' extra subfield.
+pub fn is_bgzf(path: &Path) -> Result<bool> {
+    let mut header = [0u8; 16];
+    let mut file = File::open(path)?;
+    let mut filled = 0;
+    while filled < header.len() {
+        match file.read(&mut header[filled..])? {
+            0 => break,
+            n => filled += n,
+        }
+    }
+    Ok(is_bgzf_header(&header[..filled]))
 }
 
+/// Check whether the bytes start with a BGZF block header.
+pub fn is_bgzf_header(header: &[u8]) -> bool {
+    header.len() >= 16
+        && header[0..2] == GZ_MAGIC
+        && header[2] == 8
+        && header[3] & 4 != 0
+        && header[12] == b'B'
+        && header[13] == b'C'
+        && header[14] == 2
+        && header[15] == 0
+}
+
+/// Use the Infer crate to get the mime type and extension. Returns an error if the type is not known, e.g. for plain text.
+pub fn infer_kind(path: &Path) -> Result<infer::Type> {
+    match infer::get_from_path(path)? {
+        Some(kind) => Ok(kind),
+        None => Err(BioUtilsError::UnknownFileType(path.to_path_buf())),
+    }
+}
 
+/// Detect the compression of a file from its magic bytes. Files of a known type other than gzip, e.g. zip or bz2, are an error.
+pub fn file_compression(path: &Path) -> Result<FileCompression> {
+    match infer::get_from_path(path)? {
+        None => Ok(FileCompression::Plain),
+        Some(kind) if kind.extension() == "gz" => match is_bgzf(path)? {
+            true => Ok(FileCompression::Bgzf),
+            false => Ok(FileCompression::Gzip),
+        },
+        Some(kind) => Err(BioUtilsError::UnsupportedFileType { path: path.to_path_buf(), mime_type: kind.mime_type() }),
+    }
+}
+
+/// Open a plain, gzip or BGZF file and return a buffered reader over the decompressed bytes.
+pub fn open_any(path: &Path) -> Result<Box<dyn BufRead>> {
+    let file = File::open(path)?;
+    match file_compression(path)? {
+        FileCompression::Plain => Ok(Box::new(BufReader::new(file))),
+        FileCompression::Gzip => Ok(Box::new(BufReader::new(MultiGzDecoder::new(file)))),
+        FileCompression::Bgzf => Ok(Box::new(bgzf::BgzfReader::new(BufReader::new(file)))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    #[test]
+    fn opens_plain_gzip_and_bgzf() {
+        let dir = std::env::temp_dir().join(format!("bioutils_{}_opens_plain_gzip_and_bgzf", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let plain = dir.join("open_any.fa");
+        let gz = dir.join("open_any.fa.gz");
+        std::fs::write(&plain, b">seq\nACGT\n").unwrap();
+        let mut encoder = GzEncoder::new(File::create(&gz).unwrap(), Compression::default());
+        encoder.write_all(b">seq\nACGT\n").unwrap();
+        encoder.finish().unwrap();
+        let bgzf = dir.join("open_any.fa.bgz");
+        let mut writer = bgzf::BgzfWriter::from_path(&bgzf).unwrap();
+        writer.write_all(b">seq\nACGT\n").unwrap();
+        writer.finish().unwrap();
+        assert_eq!(file_compression(&plain).unwrap(), FileCompression::Plain);
+        assert_eq!(file_compression(&gz).unwrap(), FileCompression::Gzip);
+        assert_eq!(file_compression(&bgzf).unwrap(), FileCompression::Bgzf);
+        for path in [&plain, &gz, &bgzf].iter() {
+            let mut text = String::new();
+            open_any(path).unwrap().read_to_string(&mut text).unwrap();
+            assert_eq!(text, ">seq\nACGT\n");
+        }
+        assert!(infer_kind(&plain).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
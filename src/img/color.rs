@@ -14,7 +14,7 @@
 
 use image::{Rgb};
 
-/// General colors as rgb structs with common names
+// General colors as rgb structs with common names
 
 /// Basic Colors
 /// Hex Code: #000000
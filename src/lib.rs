@@ -1,4 +1,4 @@
-use anyhow::{Result, bail};
+pub use crate::error::{BioUtilsError, Result};
 
 use std::collections::{BTreeMap, HashMap, HashSet};
 
@@ -19,6 +19,8 @@ use std::io::BufWriter;
 
 use crate::charsets::bioutils::*;
 use crate::charsets::iupac::*;
+use crate::charsets::nibble::*;
+use crate::charsets::codon::*;
 use crate::charsets::quality::*;
 use crate::charsets::ascii::*;
 use crate::charsets::percent::*;
@@ -26,6 +28,7 @@ use crate::charsets::percent::*;
 use rand::rngs::ThreadRng;
 use rand::seq::SliceRandom;
 
+pub mod error;
 pub mod charsets;
 pub mod files;
 pub mod utils;
@@ -0,0 +1,19 @@
+//! Sequencing adapter sequences as u8 arrays, as found at the 3' end of reads from inserts shorter than the read length.
+
+/// Illumina TruSeq adapter read through in read 1.
+pub const TRUSEQ_READ1: [u8; 33] = *b"AGATCGGAAGAGCACACGTCTGAACTCCAGTCA";
+/// Illumina TruSeq adapter read through in read 2.
+pub const TRUSEQ_READ2: [u8; 33] = *b"AGATCGGAAGAGCGTCGTGTAGGGAAAGAGTGT";
+/// Start shared by the TruSeq adapters, enough to detect either read.
+pub const TRUSEQ_UNIVERSAL: [u8; 13] = *b"AGATCGGAAGAGC";
+/// Illumina Nextera transposase adapter, read through in both reads.
+pub const NEXTERA: [u8; 19] = *b"CTGTCTCTTATACACATCT";
+/// Illumina small RNA 3' adapter.
+pub const SMALL_RNA: [u8; 21] = *b"TGGAATTCTCGGGTGCCAAGG";
+/// Poly-A tail.
+pub const POLY_A: [u8; 20] = *b"AAAAAAAAAAAAAAAAAAAA";
+
+/// All adapters with their names, in the order above. The universal TruSeq start is left out as both TruSeq adapters begin with it.
+pub const ADAPTERS: [(&str, &[u8]); 5] = [
+    ("TruSeq_Read1", &TRUSEQ_READ1), ("TruSeq_Read2", &TRUSEQ_READ2), ("Nextera", &NEXTERA), ("Small_RNA", &SMALL_RNA), ("PolyA", &POLY_A),
+];
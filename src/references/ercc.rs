@@ -91,4 +91,27 @@ pub const ERCC_00164: [u8;1022] = *b"AGGAGCTCCAGTAGTTTTCCCCTCAAAAATTCCTGATAAGATT
 pub const ERCC_00165: [u8;872] = *b"GATATGCGTTACGTGAGTCTGATAGCAGTTCACTACCTGGATATCTGATCCACTAGCTCGATCATGCTCACCCATAGTTTATCTGCATCACTCGTACTGAAATGCTCACATCGCAGGTAGAGCAGCATCGTAGAGCGTCAAGCTGCATCCTAGCGTCATGAGTCATAGTACCTCATGCTCACGTGATCTACCCTAGCTGACCGCTAATGACGGCAGTGCAACCTGAGATACCGACGGCATACTGTCGTCAACGTCAGGCAATGTGTCCGAACGGCGAGCTACGTCGCCTCACGGAGTAATCGCGTCCCTCTAGGTATAGTGCCGTCGGTTCAGGTCATATGTCGCGGGTTCTGCACATATCACGGACGTATCGCTATCAGACGGACGCTCTCGGACCTAAACCGTAGCTCTCGGCAAGATCGTCCTCGTCTCGAATATAGCGCCCTAGTGCTGCAAATGTCACCGCTATCTCGTAAGGGGTCCGTCTGTTGAGTTAGGCCTCCTCTCGTTGGATGTGAGCTCGGTTGCTTGGATGGTGCAGCTTACTTCGCGTACCTGCTGTTTGCATCAGTCCTCTGCATCTATAATCGCGTATCTCTCTCTAGTAGACCATATAGCCATCTAAGCGCTCGATATTCCACCTAAGTGGCGCCTATTGAACTAAGTGGCAGCCGAATGGACTATCGCTCCTCGATATGTACGGATAGGCCACGGCATGTACGAGCATAAGCCGAACTGCACGAGCATACCCGACACTGATCTGAGAGTCGCTTAAATCATCTGCGTGTCTTAGAGCTTATCGCCATGTCTGTCAACTGTACTGTCATCCTGTAACTGTAGCGTATGTGAAAAAAAAAAAAAAAAAAAAAAAA"; 
 pub const ERCC_00168: [u8;1024] = *b"CCAATGAACTCAGCTATTCTTCTTAACAAATAACTTTCTCCAGAAAATTCAAATGTATCATCTTCAGGATTCCACCTAAAAACATCATGTAATATAATATCATCAATTTTTGGGTCGTATTCAACAATCTCAGTTATACTCTCAGTTCTTCTAACAAATCTTCCTTTATAAATCAATCTAACCTGCATACATATGGCATTTAGTTGTTCAAGCATAATCTTTGGAATGTTCATTGGTTCAGCATTCAACCTCCTTATAACTGCCTCTGGGGATTTTGCGTGTATCGTTGATAACGCCAAATGTCCTGTAGTTATTGCTTGAAATAATATCTTCGCCTCCTCACCTCTAACCTCTCCAACAATTAAATAATCTGGTCTTTGCCTTAAAGCCGCTTTTAATAAATCCATCATAGTTATTTCATATTCTTCTCCACCGAATCCACTTCTTGTAGTTCCAGCAATCCAGTTTTCATGATACAACCTAATTTCTGGAGTATCCTCAATAGATACGATTTTCATTTGAGGAAGGATGAAAAGAGAGAATGCATTTAAAAGGGTGGTTTTTCCAGTAGCTACCTCTCCAGCAACCATAATAGAATTTTTATATTCAATGAGTAACCAAAGATATGCAAGCATCTCTGGAGAAATACTCCCATATCTTATTAAATCTGTTGGCAATATAGGAGTGTGTGTGAATTTTCTTATTGTAAATGTTGAACCATATCTTGAGATATCCCTTCCAAGGGTTACATTTAGCCTGCTACCATCTGGGAGAGAACCATCCACTATTGGATTAGCCAATGTTAAAGATTTTCCACACCTTTGGGCTAAGGATATACAAAACGAGTCTAATTCTTCATCAGTTTCAAATTTTATATTTGTCTTTAAATGTTCGTATTTTCTATGAAACACATACACTGGCTTTCCAACACCTGTGCAACTGATATCCTCCAAATTCTCATCTTTCATAAGAGCATCTATTTCCCATATCCAATGAGGTAAAAAAAAAAAAAAAAAAAAAAAAA"; 
 pub const ERCC_00170: [u8;1023] = *b"TATTGGTGGAGGGGCACAAGTTGCTGAAGTTGCGAGAGGGGCGATAAGTGAGGCAGACAGGCATAATATAAGAGGGGAGAGAATTAGCGTAGATACTCTTCCAATAGTTGGTGAAGAAAATTTATATGAGGCTGTTAAAGCTGTAGCAACTCTTCCACGAGTAGGAATTTTAGTTTTAGCTGGCTCTTTAATGGGAGGGAAGATAACTGAAGCAGTTAAAGAATTAAAGGAAAAGACTGGCATTCCCGTGATAAGCTTAAAGATGTTTGGCTCTGTTCCTAAGGTTGCTGATTTGGTTGTTGGAGACCCATTGCAGGCAGGGGTTTTAGCTGTTATGGCTATTGCTGAAACAGCAAAATTTGATATAAATAAGGTTAAAGGTAGGGTGCTATAAAGATAATTTAATAATTTTTGATGAAACCGAAGCGTTAGCTTTGGGTTATGAAACTCCATGATTTTCATTTAATTTTTTCCTATTAATTTTCTCCTAAAAAGTTTCTTTAACATAAATAAGGTTAAAGGGAGAGCTCTATGATTGTCTTCAAAAATACAAAGATTATTGATGTATATACTGGAGAGGTTGTTAAAGGAAATGTTGCAGTTGAGAGGGATAAAATATCCTTTGTGGATTTAAATGATGAAATTGATAAGATAATTGAAAAAATAAAGGAGGATGTTAAAGTTATTGACTTAAAAGGAAAATATTTATCTCCAACATTTATAGATGGGCATATACATATAGAATCTTCCCATCTCATCCCATCAGAGTTTGAGAAATTTGTATTAAAAAGCGGAGTTAGCAAAGTAGTTATAGACCCGCATGAAATAGCAAATATTGCTGGAAAAGAAGGAATTTTGTTTATGTTGAATGATGCCAAAATTTTAGATGTCTATGTTATGCTTCCTTCCTGTGTTCCAGCTACAAACTTAGAAACAAGTGGAGCTGAGATTACAGCAGAGAATATTGAAGAACTCATTCTTTAGATAATGTCTTAGGTTAAAAAAAAAAAAAAAAAAAAAAAA"; 
-pub const ERCC_00171: [u8;505] = *b"CTGGAGATTGTCTCGTACGGTTAAGAGCCTCCGCCCGTCTCTGGGACTATGGACGGGCACGCTCATATCAGGCTATATTTGGTCCGGGTTATTATCGTCGCGGTTACCGTAATACTTCAGATCAGTTAAGTAGGGCCATATGCCTCGGGAATAAGCTGACGGTGACAAGGTTTCCCCCTAATCGAGACGCTGCAATAACACAGGGGCATACAGTAACCAGGCAAGAGTTCAATCGCTTAGTTTCGTGGCGGGATTTGAGGAAAACTGCGACTGTTCTTTAACCAAACATCCGTGCGATTCGTGCCACTCGTAGACGGCATCTCACAGTCACTGAAGGCTATTAAAGAGTTAGCACCCACCATTGGATGAAGCCCAGGATAAGTGACCCCCCCGGACCTTGGAGTTTCATGCTAATCAAAGAAGAGCTAATCCGACGTAAAGTTGCGGCGTTGATTACGCAGGATTGCGACCAAAGAACGAGAAAAAAAAAAAAAAAAAAAAAAAA"; 
+pub const ERCC_00171: [u8;505] = *b"CTGGAGATTGTCTCGTACGGTTAAGAGCCTCCGCCCGTCTCTGGGACTATGGACGGGCACGCTCATATCAGGCTATATTTGGTCCGGGTTATTATCGTCGCGGTTACCGTAATACTTCAGATCAGTTAAGTAGGGCCATATGCCTCGGGAATAAGCTGACGGTGACAAGGTTTCCCCCTAATCGAGACGCTGCAATAACACAGGGGCATACAGTAACCAGGCAAGAGTTCAATCGCTTAGTTTCGTGGCGGGATTTGAGGAAAACTGCGACTGTTCTTTAACCAAACATCCGTGCGATTCGTGCCACTCGTAGACGGCATCTCACAGTCACTGAAGGCTATTAAAGAGTTAGCACCCACCATTGGATGAAGCCCAGGATAAGTGACCCCCCCGGACCTTGGAGTTTCATGCTAATCAAAGAAGAGCTAATCCGACGTAAAGTTGCGGCGTTGATTACGCAGGATTGCGACCAAAGAACGAGAAAAAAAAAAAAAAAAAAAAAAAA";
+
+/// All ERCC sequences with their names, in the order above. Used to write the ERCC spike-ins as a multi-record FASTA.
+pub const ERCC: [(&str, &[u8]); 92] = [
+    ("ERCC-00002", &ERCC_00002), ("ERCC-00003", &ERCC_00003), ("ERCC-00004", &ERCC_00004), ("ERCC-00009", &ERCC_00009), ("ERCC-00012", &ERCC_00012),
+    ("ERCC-00013", &ERCC_00013), ("ERCC-00014", &ERCC_00014), ("ERCC-00016", &ERCC_00016), ("ERCC-00017", &ERCC_00017), ("ERCC-00019", &ERCC_00019),
+    ("ERCC-00022", &ERCC_00022), ("ERCC-00024", &ERCC_00024), ("ERCC-00025", &ERCC_00025), ("ERCC-00028", &ERCC_00028), ("ERCC-00031", &ERCC_00031),
+    ("ERCC-00033", &ERCC_00033), ("ERCC-00034", &ERCC_00034), ("ERCC-00035", &ERCC_00035), ("ERCC-00039", &ERCC_00039), ("ERCC-00040", &ERCC_00040),
+    ("ERCC-00041", &ERCC_00041), ("ERCC-00042", &ERCC_00042), ("ERCC-00043", &ERCC_00043), ("ERCC-00044", &ERCC_00044), ("ERCC-00046", &ERCC_00046),
+    ("ERCC-00048", &ERCC_00048), ("ERCC-00051", &ERCC_00051), ("ERCC-00053", &ERCC_00053), ("ERCC-00054", &ERCC_00054), ("ERCC-00057", &ERCC_00057),
+    ("ERCC-00058", &ERCC_00058), ("ERCC-00059", &ERCC_00059), ("ERCC-00060", &ERCC_00060), ("ERCC-00061", &ERCC_00061), ("ERCC-00062", &ERCC_00062),
+    ("ERCC-00067", &ERCC_00067), ("ERCC-00069", &ERCC_00069), ("ERCC-00071", &ERCC_00071), ("ERCC-00073", &ERCC_00073), ("ERCC-00074", &ERCC_00074),
+    ("ERCC-00075", &ERCC_00075), ("ERCC-00076", &ERCC_00076), ("ERCC-00077", &ERCC_00077), ("ERCC-00078", &ERCC_00078), ("ERCC-00079", &ERCC_00079),
+    ("ERCC-00081", &ERCC_00081), ("ERCC-00083", &ERCC_00083), ("ERCC-00084", &ERCC_00084), ("ERCC-00085", &ERCC_00085), ("ERCC-00086", &ERCC_00086),
+    ("ERCC-00092", &ERCC_00092), ("ERCC-00095", &ERCC_00095), ("ERCC-00096", &ERCC_00096), ("ERCC-00097", &ERCC_00097), ("ERCC-00098", &ERCC_00098),
+    ("ERCC-00099", &ERCC_00099), ("ERCC-00104", &ERCC_00104), ("ERCC-00108", &ERCC_00108), ("ERCC-00109", &ERCC_00109), ("ERCC-00111", &ERCC_00111),
+    ("ERCC-00112", &ERCC_00112), ("ERCC-00113", &ERCC_00113), ("ERCC-00116", &ERCC_00116), ("ERCC-00117", &ERCC_00117), ("ERCC-00120", &ERCC_00120),
+    ("ERCC-00123", &ERCC_00123), ("ERCC-00126", &ERCC_00126), ("ERCC-00130", &ERCC_00130), ("ERCC-00131", &ERCC_00131), ("ERCC-00134", &ERCC_00134),
+    ("ERCC-00136", &ERCC_00136), ("ERCC-00137", &ERCC_00137), ("ERCC-00138", &ERCC_00138), ("ERCC-00142", &ERCC_00142), ("ERCC-00143", &ERCC_00143),
+    ("ERCC-00144", &ERCC_00144), ("ERCC-00145", &ERCC_00145), ("ERCC-00147", &ERCC_00147), ("ERCC-00148", &ERCC_00148), ("ERCC-00150", &ERCC_00150),
+    ("ERCC-00154", &ERCC_00154), ("ERCC-00156", &ERCC_00156), ("ERCC-00157", &ERCC_00157), ("ERCC-00158", &ERCC_00158), ("ERCC-00160", &ERCC_00160),
+    ("ERCC-00162", &ERCC_00162), ("ERCC-00163", &ERCC_00163), ("ERCC-00164", &ERCC_00164), ("ERCC-00165", &ERCC_00165), ("ERCC-00168", &ERCC_00168),
+    ("ERCC-00170", &ERCC_00170), ("ERCC-00171", &ERCC_00171),
+];
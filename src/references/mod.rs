@@ -6,36 +6,52 @@ use super::*;
 pub mod ftp;
 pub mod ercc;
 pub mod phix;
+pub mod adapters;
+
+use crate::files::fasta::FastaWriter;
+use ercc::ERCC;
+use phix::{PHIX_GENOME, PHIX_NAME};
+use adapters::ADAPTERS;
 
-use std::io::{Write};
 use std::fs::File;
-use curl::easy::Easy;
-use url::{Url, ParseError};
+use crate::files::http::curl_to_file;
+use url::Url;
 
 /// Function to intake a base url and a filename and paste them together to generate a url where the file may be found.
-pub fn build_url(url: &str, filename: &str) -> Result<Url, ParseError> {
-    let base = Url::parse(url).expect("Base url cannot be parsed");
+pub fn build_url(url: &str, filename: &str) -> Result<Url> {
+    let base = Url::parse(url)?;
     let joined = base.join(filename)?;
     Ok(joined)
 }
 
 /// Function to download a reference from a base url and a filename.
 /// This function only needs to be completed once for the reference of choice, as the file will be stored on the hard drive.
-pub fn download_reference(url: &str, filename: &str) {
-    // Generate url to get file
-    let mut easy = Easy::new();
-    let file_url = build_url(url, filename).expect("Cannot build file url").to_string();
-    easy.url(&file_url).unwrap();
-    // Create file on system to write on
-    let mut file = match File::create(filename) {
-        Err(why) => panic!("couldn't create {}", why),
-        Ok(file) => file,
-    };
-    // Write to file on system
-    easy.write_function(move |data| {
-        file.write_all(data).unwrap();
-        Ok(data.len())
-    }).unwrap();
-    easy.perform().unwrap();
-    // println!("{}", easy.response_code().unwrap());
+pub fn download_reference(url: &str, filename: &str) -> Result<()> {
+    let file_url = build_url(url, filename)?.to_string();
+    curl_to_file(&file_url, Path::new(filename))
+}
+
+/// Write the embedded phiX genome to a FASTA file, wrapping lines at the given width (0 for no wrapping).
+pub fn write_phix_fasta(path: &Path, line_width: usize) -> Result<()> {
+    let mut writer = FastaWriter::with_line_width(BufWriter::new(File::create(path)?), line_width);
+    writer.write(PHIX_NAME.as_bytes(), &PHIX_GENOME)?;
+    writer.flush()
+}
+
+/// Write the embedded ERCC spike-in sequences to a multi-record FASTA file, wrapping lines at the given width (0 for no wrapping).
+pub fn write_ercc_fasta(path: &Path, line_width: usize) -> Result<()> {
+    let mut writer = FastaWriter::with_line_width(BufWriter::new(File::create(path)?), line_width);
+    for (name, seq) in ERCC.iter() {
+        writer.write(name.as_bytes(), seq)?;
+    }
+    writer.flush()
+}
+
+/// Write the embedded adapter sequences to a multi-record FASTA file, e.g. for other trimmers.
+pub fn write_adapter_fasta(path: &Path) -> Result<()> {
+    let mut writer = FastaWriter::with_line_width(BufWriter::new(File::create(path)?), 0);
+    for (name, seq) in ADAPTERS.iter() {
+        writer.write(name.as_bytes(), seq)?;
+    }
+    writer.flush()
 }
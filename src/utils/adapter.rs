@@ -0,0 +1,243 @@
+//! Adapter detection and trimming by 3' overlap with mismatches, and merging of overlapping paired-end reads. Adapters are compared without indels, either case, against the library in references::adapters or any other.
+//! # Examples
+//! ```
+//! use bioutils::references::adapters::ADAPTERS;
+//! use bioutils::utils::adapter::*;
+//! let params = OverlapParams::adapter();
+//! // 12 bases of insert, then the TruSeq adapter with one mismatch
+//! let read = b"ACGTTGCAACGTAGATCGGTAGAGCACACG";
+//! let (adapter, found) = read.detect_adapter(&ADAPTERS, &params).unwrap();
+//! assert_eq!(ADAPTERS[adapter].0, "TruSeq_Read1");
+//! assert_eq!(found, AdapterMatch { position: 12, overlap: 18, mismatches: 1 });
+//! assert_eq!(read.trim_adapters(&ADAPTERS, &params), 0..12);
+//! // Partial adapters at the very end are found down to the minimum overlap
+//! assert_eq!(b"ACGTTGCAACGTAGAT".trim_adapters(&ADAPTERS, &params), 0..12);
+//! // 12 base mates of a 14 base insert overlap by 10 bases
+//! let insert = b"ACGTTGCAGGCATT";
+//! let r2 = b"AATGCCTGCAAC"; // reverse complement of the last 12 bases
+//! let (seq, qual) = merge_pair(&insert[..12], b"IIIIIIIIIIII", r2, b"IIIIIIIIIIII", &OverlapParams::paired_end()).unwrap();
+//! assert_eq!(seq, insert.to_vec());
+//! assert_eq!(qual.len(), 14);
+//! ```
+
+use super::*;
+use std::ops::Range;
+use crate::files::fastq::FastqOwnedRecord;
+use crate::utils::recode::BioUtilsAsRefDoubleEndedIterator;
+
+/// Minimum overlap and maximum mismatches per overlapping base for adapter and read overlaps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverlapParams {
+    pub min_overlap: usize,
+    pub max_error_rate: f64,
+}
+
+impl OverlapParams {
+    /// Adapter trimming as cutadapt, 3 bases overlap and 10% mismatches.
+    pub const fn adapter() -> OverlapParams {
+        OverlapParams { min_overlap: 3, max_error_rate: 0.1 }
+    }
+    /// Paired-end merging, 10 bases overlap and 10% mismatches.
+    pub const fn paired_end() -> OverlapParams {
+        OverlapParams { min_overlap: 10, max_error_rate: 0.1 }
+    }
+    /// Number of mismatches allowed in an overlap of the length.
+    pub fn max_mismatches(&self, overlap: usize) -> usize {
+        (overlap as f64 * self.max_error_rate) as usize
+    }
+}
+
+impl Default for OverlapParams {
+    fn default() -> OverlapParams {
+        OverlapParams::adapter()
+    }
+}
+
+/// Adapter found in a read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdapterMatch {
+    /// Start of the adapter in the read, the read is trimmed to 0..position.
+    pub position: usize,
+    /// Number of adapter bases in the read.
+    pub overlap: usize,
+    pub mismatches: usize,
+}
+
+// Number of mismatches, or None once there are more than max.
+fn mismatches(a: &[u8], b: &[u8], max: usize) -> Option<usize> {
+    let mut count = 0;
+    for (x, y) in a.iter().zip(b.iter()) {
+        if !x.eq_ignore_ascii_case(y) {
+            count += 1;
+            if count > max {
+                return None
+            }
+        }
+    }
+    Some(count)
+}
+
+pub trait AdapterAsRefSlice<T> {
+    /// Finds the leftmost occurrence of the adapter that is complete or runs off the 3' end of the read with at least the minimum overlap.
+    fn find_adapter(&self, adapter: &[u8], params: &OverlapParams) -> Option<AdapterMatch>;
+    /// Finds the adapter of the library that starts first, with the index of the adapter. Ties go to the match with fewer mismatches.
+    fn detect_adapter(&self, adapters: &[(&str, &[u8])], params: &OverlapParams) -> Option<(usize, AdapterMatch)>;
+    /// Returns the range of the read before the first adapter of the library, the whole read if there is none.
+    fn trim_adapters(&self, adapters: &[(&str, &[u8])], params: &OverlapParams) -> Range<usize>;
+}
+
+impl<T> AdapterAsRefSlice<T> for T where
+T: AsRef<[u8]>,
+{
+    /// Finds the leftmost occurrence of the adapter that is complete or runs off the 3' end of the read with at least the minimum overlap.
+    fn find_adapter(&self, adapter: &[u8], params: &OverlapParams) -> Option<AdapterMatch> {
+        let read = self.as_ref();
+        let min_overlap = params.min_overlap.max(1).min(adapter.len());
+        if adapter.is_empty() || read.len() < min_overlap {
+            return None
+        }
+        (0..=read.len() - min_overlap).find_map(|position| {
+            let overlap = adapter.len().min(read.len() - position);
+            mismatches(&read[position..position + overlap], &adapter[..overlap], params.max_mismatches(overlap))
+                .map(|mismatches| AdapterMatch { position, overlap, mismatches })
+        })
+    }
+    /// Finds the adapter of the library that starts first, with the index of the adapter. Ties go to the match with fewer mismatches.
+    fn detect_adapter(&self, adapters: &[(&str, &[u8])], params: &OverlapParams) -> Option<(usize, AdapterMatch)> {
+        adapters.iter().enumerate()
+            .filter_map(|(i, (_, adapter))| self.find_adapter(adapter, params).map(|found| (i, found)))
+            .min_by_key(|(_, found)| (found.position, found.mismatches))
+    }
+    /// Returns the range of the read before the first adapter of the library, the whole read if there is none.
+    fn trim_adapters(&self, adapters: &[(&str, &[u8])], params: &OverlapParams) -> Range<usize> {
+        match self.detect_adapter(adapters, params) {
+            Some((_, found)) => 0..found.position,
+            None => 0..self.as_ref().len(),
+        }
+    }
+}
+
+/// Merges a read pair whose mates overlap into the sequence and quality of the insert. Mate 2 is reverse complemented (IUPAC codes of either case), and every shift of the mates with at least the minimum overlap is scored. The shift with the lowest mismatch rate wins, ties going to the longer overlap. In the overlap the base with the higher quality wins, and on a mismatch its Phred+33 quality drops to the difference of the two scores, at least 2, as FLASH does. Inserts shorter than the reads are cut to the overlap, which drops the adapters. None if the mates do not overlap.
+pub fn merge_pair(seq1: &[u8], qual1: &[u8], seq2: &[u8], qual2: &[u8], params: &OverlapParams) -> Option<(Vec<u8>, Vec<u8>)> {
+    if seq1.len() != qual1.len() || seq2.len() != qual2.len() {
+        return None
+    }
+    let rev_seq2 = seq2.rev_recode(BioUtilsRecodeSet::IupacComplementMixCase)?;
+    let rev_qual2: Vec<u8> = qual2.iter().rev().copied().collect();
+    let min_overlap = params.min_overlap.max(1);
+    if seq1.len() < min_overlap || seq2.len() < min_overlap {
+        return None
+    }
+    // Start of mate 2 relative to mate 1, negative if mate 2 starts before mate 1 as when reading through a short insert
+    let shifts = (0..=(seq1.len() - min_overlap) as isize).chain((1..=(seq2.len() - min_overlap) as isize).map(|s| -s));
+    let mut best: Option<(isize, usize, usize)> = None;
+    for shift in shifts {
+        let (start1, start2) = match shift >= 0 {
+            true => (shift as usize, 0),
+            false => (0, (-shift) as usize),
+        };
+        let overlap = (seq1.len() - start1).min(seq2.len() - start2);
+        let count = match mismatches(&seq1[start1..start1 + overlap], &rev_seq2[start2..start2 + overlap], params.max_mismatches(overlap)) {
+            Some(count) if overlap >= min_overlap => count,
+            _ => continue,
+        };
+        // Lower mismatch rate, count / overlap, or the same rate over a longer overlap
+        let better = match best {
+            Some((_, best_overlap, best_count)) => match (count * best_overlap).cmp(&(best_count * overlap)) {
+                Ordering::Less => true,
+                Ordering::Equal => overlap > best_overlap,
+                Ordering::Greater => false,
+            },
+            None => true,
+        };
+        if better {
+            best = Some((shift, overlap, count));
+        }
+    }
+    let (shift, overlap, _) = best?;
+    let (start1, start2) = match shift >= 0 {
+        true => (shift as usize, 0),
+        false => (0, (-shift) as usize),
+    };
+    let mut seq = seq1[..start1].to_vec();
+    let mut qual = qual1[..start1].to_vec();
+    for i in 0..overlap {
+        let (base1, q1, base2, q2) = (seq1[start1 + i], qual1[start1 + i], rev_seq2[start2 + i], rev_qual2[start2 + i]);
+        seq.push(if q2 > q1 { base2 } else { base1 });
+        qual.push(match base1.eq_ignore_ascii_case(&base2) {
+            true => q1.max(q2),
+            false => (q1.max(q2) - q1.min(q2)).max(2) + 33,
+        });
+    }
+    if shift >= 0 {
+        seq.extend_from_slice(&rev_seq2[overlap..]);
+        qual.extend_from_slice(&rev_qual2[overlap..]);
+    }
+    Some((seq, qual))
+}
+
+/// Merges the mates of a FASTQ pair, keeping the header of mate 1. See merge_pair.
+pub fn merge_records(mate1: &FastqOwnedRecord, mate2: &FastqOwnedRecord, params: &OverlapParams) -> Option<FastqOwnedRecord> {
+    let (seq, qual) = merge_pair(&mate1.seq, &mate1.qual, &mate2.seq, &mate2.qual, params)?;
+    Some(FastqOwnedRecord { head: mate1.head.clone(), seq, qual })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::references::adapters::*;
+
+    #[test]
+    fn trims_adapters() {
+        let params = OverlapParams::adapter();
+        let insert = b"TTGACCGATACCGGTTAAGC";
+        assert_eq!(insert.detect_adapter(&ADAPTERS, &params), None);
+        let read = [&insert[..], &NEXTERA[..]].concat();
+        assert_eq!(read.trim_adapters(&ADAPTERS, &params), 0..20);
+        let polya = [&insert[..], b"aaaa"].concat();
+        assert_eq!(polya.detect_adapter(&ADAPTERS, &params).unwrap().0, 4);
+        // Two mismatches in 10 bases are too many
+        assert_eq!([&insert[..], b"AGTTCGGTAG"].concat().find_adapter(&TRUSEQ_READ1, &params), None);
+        assert_eq!(b"TTGACAGATC".find_adapter(&TRUSEQ_UNIVERSAL, &params), Some(AdapterMatch { position: 5, overlap: 5, mismatches: 0 }));
+    }
+
+    #[test]
+    fn merges_pairs() {
+        let params = OverlapParams::paired_end();
+        // 14 base insert read through into the adapters
+        let insert = b"ACGTTGCAGGCATT";
+        let mate1 = [&insert[..], &TRUSEQ_READ1[..6]].concat();
+        let mate2 = [&insert.rev_recode(BioUtilsRecodeSet::DnaComplement).unwrap()[..], &TRUSEQ_READ2[..6]].concat();
+        let mut qual2 = vec![b'I'; 20];
+        qual2[0] = b'#';
+        let record1 = FastqOwnedRecord { head: b"pair".to_vec(), seq: mate1, qual: vec![b'I'; 20] };
+        let mut record2 = FastqOwnedRecord { head: b"pair".to_vec(), seq: mate2, qual: qual2 };
+        let merged = merge_records(&record1, &record2, &params).unwrap();
+        assert_eq!(merged.seq, insert.to_vec());
+        assert_eq!(merged.qual, vec![b'I'; 14]);
+        // A low quality mismatch in mate 2 loses to mate 1, and lowers the quality from Q40 to Q38
+        record2.seq[0] = b'G';
+        let merged = merge_records(&record1, &record2, &params).unwrap();
+        assert_eq!(merged.seq, insert.to_vec());
+        assert_eq!(merged.qual[13], b'G');
+        // Equal qualities of a mismatch drop to Q2
+        let (_, qual) = merge_pair(b"ACGTTGCAGG", b"IIIIIIIIII", b"CCTGCAACGA", b"IIIIIIIIII", &params).unwrap();
+        assert_eq!(qual[0], b'#');
+        record2.seq = b"GGGGGGGGGGGGGGGGGGGG".to_vec();
+        assert_eq!(merge_records(&record1, &record2, &params), None);
+    }
+
+    #[test]
+    fn merges_read_through_over_spurious_overlap() {
+        let params = OverlapParams::paired_end();
+        // 14 base insert, and the adapters make mate 1 shifted by 10 match mate 2 with one mismatch
+        let insert = b"ACGTTGCAGGCATT";
+        let mate1 = [&insert[..], b"GAACGA"].concat();
+        let mate2 = [&insert.rev_recode(BioUtilsRecodeSet::DnaComplement).unwrap()[..], b"TCAATG"].concat();
+        let rev_mate2 = mate2.rev_recode(BioUtilsRecodeSet::DnaComplement).unwrap();
+        assert_eq!(mismatches(&mate1[10..], &rev_mate2[..10], 1), Some(1));
+        let (seq, qual) = merge_pair(&mate1, &[b'I'; 20], &mate2, &[b'I'; 20], &params).unwrap();
+        assert_eq!(seq, insert.to_vec());
+        assert_eq!(qual, vec![b'I'; 14]);
+    }
+}
@@ -22,7 +22,7 @@ T: AsRef<[u8]>,
     }
     /// Returns the mean of u8s as usize
     fn usize_mean(&self) -> usize {
-        self.as_ref().iter().map(|x| *x as usize).sum::<usize>() / (self.as_ref().len() as usize)
+        self.as_ref().iter().map(|x| *x as usize).sum::<usize>() / self.as_ref().len()
     }
     /// Returns the mode of u8s
     fn u8_mode(&self)-> Option<u8> {
@@ -65,7 +65,7 @@ T: AsMut<[u8]>
     
     /// Returns the mean of u8s as usize
     fn mut_usize_mean(&mut self) -> usize {
-        self.as_mut().iter().map(|x| *x as usize).sum::<usize>() / (self.as_mut().len() as usize)
+        self.as_mut().iter().map(|x| *x as usize).sum::<usize>() / self.as_mut().len()
     }
 
     /// Returns the mode of u8s
@@ -10,9 +10,33 @@
 //! println!("{:?}", dna_reference_2.result_is_all_charset_with(&[67u8, 68u8]));
 //! let dna_reference_3 = &[67u8,68u8,67u8,67u8];
 //! println!("{:?}", dna_reference_3.result_is_all_charset_with(&[67u8,68u8]).unwrap().is_all_charset_with(&[5u8,6u8,7u8]));
+//! // Report every byte not in the character set with its position.
+//! use bioutils::utils::check::ValidateAsRefSlice;
+//! let validation = b"ACXTXA.".validate(BioUtilsCharSet::Dna);
+//! assert_eq!(validation.to_string(), "1 '.' at 6; 2 'X' at 2, 4");
 //! ```
 
 use super::*;
+use crate::utils::simd;
+
+/// Returns an InvalidCharacter error for the first byte of the slice not in the charset.
+fn first_invalid(slice: &[u8], table: &BioUtilsByteSet) -> Option<BioUtilsError> {
+    let position = slice.iter().position(|u| !table.contains(*u))?;
+    Some(BioUtilsError::InvalidCharacter { position, byte: slice[position] })
+}
+
+/// Checks the slice against the charset table, using the SIMD path for the DNA charsets.
+fn is_all_table(slice: &[u8], charset: BioUtilsCharSet) -> bool {
+    match charset {
+        BioUtilsCharSet::Dna => simd::is_all_dna(slice),
+        BioUtilsCharSet::DnaMixCase => simd::is_all_dna_mix_case(slice),
+        BioUtilsCharSet::Dnan => simd::is_all_dnan(slice),
+        charset => {
+            let table = charset.table();
+            slice.iter().all(|u| table.contains(*u))
+        },
+    }
+}
 
 pub trait AllAsRefSlice<T> {
     /// Checks if all elements in the slice are contained in a character set (bool).
@@ -35,13 +59,13 @@ T: AsRef<[u8]>
 {
     /// Checks if all elements in the slice are contained in a character set (bool).
     fn is_all_charset(&self, charset: BioUtilsCharSet) -> bool {
-        self.as_ref().iter().all(|u| charset.value().contains(u))
+        is_all_table(self.as_ref(), charset)
     }
     /// Checks if all elements in the slice are contained in a character set (Ok if true, Err if false).
     fn result_is_all_charset(&self, charset: BioUtilsCharSet) -> Result<&Self> {
-        match self.is_all_charset(charset) {
-            true => Ok(self),
-            false => bail!("Slice is not all charset"),
+        match first_invalid(self.as_ref(), charset.table()) {
+            None => Ok(self),
+            Some(e) => Err(e),
         }
     }
     /// Checks if all elements in the slice are contained in a character set (Some if true, None if false).
@@ -54,13 +78,14 @@ T: AsRef<[u8]>
 
     /// Checks if all elements in the slice are contained in a character set (bool).
     fn is_all_charset_with(&self, charset: &[u8]) -> bool {
-        self.as_ref().iter().all(|u| charset.contains(u))
+        let table = BioUtilsByteSet::new(charset);
+        self.as_ref().iter().all(|u| table.contains(*u))
     }
     /// Checks if all elements in the slice are contained in a character set (Ok if true, Err if false).
     fn result_is_all_charset_with(&self, charset: &[u8]) -> Result<&Self> {
-        match self.is_all_charset_with(charset) {
-            true => Ok(self),
-            false => bail!("Slice is not all charset"),
+        match first_invalid(self.as_ref(), &BioUtilsByteSet::new(charset)) {
+            None => Ok(self),
+            Some(e) => Err(e),
         }
     }
     /// Checks if all elements in the slice are contained in a character set (Some if true, None if false).
@@ -93,13 +118,14 @@ T: AsRef<[u8]>
 {
     /// Checks if the slice contains any from the character set (bool).
     fn has_charset(&self, charset: BioUtilsCharSet) -> bool {
-        self.as_ref().iter().any(|u| charset.value().contains(u))
+        let table = charset.table();
+        self.as_ref().iter().any(|u| table.contains(*u))
     }
     /// Checks if the slice contains any from the character set (Ok if true, Err if false).
     fn result_has_charset(&self, charset: BioUtilsCharSet) -> Result<&Self> {
         match self.has_charset(charset) {
             true => Ok(self),
-            false => bail!("Slice does not have the charset"),
+            false => Err(BioUtilsError::MissingCharacter),
         }
     }
     /// Checks if the slice contains any from the character set (Some if true, None if false).
@@ -112,13 +138,14 @@ T: AsRef<[u8]>
 
     /// Checks if the slice contains any from the character set (bool).
     fn has_charset_with(&self, charset: &[u8]) -> bool {
-        self.as_ref().iter().any(|u| charset.contains(u))
+        let table = BioUtilsByteSet::new(charset);
+        self.as_ref().iter().any(|u| table.contains(*u))
     }
     /// Checks if the slice contains any from the character set (Ok if true, Err if false).
     fn result_has_charset_with(&self, charset: &[u8]) -> Result<&Self> {
         match self.has_charset_with(charset) {
             true => Ok(self),
-            false => bail!("Slice does not have the charset"),
+            false => Err(BioUtilsError::MissingCharacter),
         }
     }
     /// Checks if the slice contains any from the character set (Some if true, None if false).
@@ -152,13 +179,13 @@ T: AsMut<[u8]>
 {
     /// Checks if all elements in the slice are contained in a character set (bool).
     fn mut_is_all_charset(&mut self, charset: BioUtilsCharSet) -> bool {
-        self.as_mut().iter().all(|u| charset.value().contains(u))
+        is_all_table(self.as_mut(), charset)
     }
     /// Checks if all elements in the slice are contained in a character set (Ok if true, Err if false).
     fn mut_result_is_all_charset(&mut self, charset: BioUtilsCharSet) -> Result<&mut Self> {
-        match self.mut_is_all_charset(charset) {
-            true => Ok(self),
-            false => bail!("Slice is not all charset"),
+        match first_invalid(self.as_mut(), charset.table()) {
+            None => Ok(self),
+            Some(e) => Err(e),
         }
     }
     /// Checks if all elements in the slice are contained in a character set (Some if true, None if false).
@@ -171,13 +198,14 @@ T: AsMut<[u8]>
 
     /// Checks if all elements in the slice are contained in a character set (bool).
     fn mut_is_all_charset_with(&mut self, charset: &[u8]) -> bool {
-        self.as_mut().iter().all(|u| charset.contains(u))
+        let table = BioUtilsByteSet::new(charset);
+        self.as_mut().iter().all(|u| table.contains(*u))
     }
     /// Checks if all elements in the slice are contained in a character set (Ok if true, Err if false).
     fn mut_result_is_all_charset_with(&mut self, charset: &[u8]) -> Result<&mut Self> {
-        match self.mut_is_all_charset_with(charset) {
-            true => Ok(self),
-            false => bail!("Slice is not all charset"),
+        match first_invalid(self.as_mut(), &BioUtilsByteSet::new(charset)) {
+            None => Ok(self),
+            Some(e) => Err(e),
         }
     }
     /// Checks if all elements in the slice are contained in a character set (Some if true, None if false).
@@ -189,7 +217,7 @@ T: AsMut<[u8]>
     }
 }
 
-trait AnyAsMutSlice<T> {
+pub trait AnyAsMutSlice<T> {
     /// Checks if the slice contains any from the character set (bool).
     fn mut_has_charset(&mut self, charset: BioUtilsCharSet) -> bool;
     /// Checks if the slice contains any from the character set (Ok if true, Err if false).
@@ -210,13 +238,14 @@ T: AsMut<[u8]>,
 {
     /// Checks if the slice contains any from the character set (bool).
     fn mut_has_charset(&mut self, charset: BioUtilsCharSet) -> bool {
-        self.as_mut().iter().any(|u| charset.value().contains(u))
+        let table = charset.table();
+        self.as_mut().iter().any(|u| table.contains(*u))
     }
     /// Checks if the slice contains any from the character set (Ok if true, Err if false).
     fn mut_result_has_charset(&mut self, charset: BioUtilsCharSet) -> Result<&mut Self> {
         match self.mut_has_charset(charset) {
             true => Ok(self),
-            false => bail!("Slice does not have the charset"),
+            false => Err(BioUtilsError::MissingCharacter),
         }
     }
     /// Checks if the slice contains any from the character set (Some if true, None if false).
@@ -228,13 +257,14 @@ T: AsMut<[u8]>,
     }
     /// Checks if the slice contains any from the character set (bool).
     fn mut_has_charset_with(&mut self, charset: &[u8]) -> bool {
-        self.as_mut().iter().any(|u| charset.contains(u))
+        let table = BioUtilsByteSet::new(charset);
+        self.as_mut().iter().any(|u| table.contains(*u))
     }
     /// Checks if the slice contains any from the character set (Ok if true, Err if false).
     fn mut_result_has_charset_with(&mut self, charset: &[u8]) -> Result<&mut Self> {
         match self.mut_has_charset_with(charset) {
             true => Ok(self),
-            false => bail!("Slice does not have the charset"),
+            false => Err(BioUtilsError::MissingCharacter),
         }
     }
     /// Checks if the slice contains any from the character set (Some if true, None if false).
@@ -246,6 +276,82 @@ T: AsMut<[u8]>,
     }
 }
 
+pub trait ValidateAsRefSlice<T> {
+    /// Returns every byte not in the character set with its position.
+    fn validate(&self, charset: BioUtilsCharSet) -> CharsetValidation;
+    /// Returns every byte not in the character set with its position.
+    fn validate_with(&self, charset: &[u8]) -> CharsetValidation;
+}
+
+impl<T> ValidateAsRefSlice<T> for T where
+T: AsRef<[u8]>,
+{
+    /// Returns every byte not in the character set with its position.
+    fn validate(&self, charset: BioUtilsCharSet) -> CharsetValidation {
+        validate_table(self.as_ref(), charset.table())
+    }
+    /// Returns every byte not in the character set with its position.
+    fn validate_with(&self, charset: &[u8]) -> CharsetValidation {
+        validate_table(self.as_ref(), &BioUtilsByteSet::new(charset))
+    }
+}
+
+fn validate_table(slice: &[u8], table: &BioUtilsByteSet) -> CharsetValidation {
+    let invalid = slice.iter().enumerate()
+        .filter(|(_, u)| !table.contains(**u))
+        .map(|(position, u)| (position, *u))
+        .collect::<Vec<(usize, u8)>>();
+    CharsetValidation { invalid }
+}
+
+/// Invalid bytes found by validate, in order of position.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CharsetValidation {
+    pub invalid: Vec<(usize, u8)>,
+}
+
+impl CharsetValidation {
+    /// True if every byte is in the character set.
+    pub fn is_valid(&self) -> bool {
+        self.invalid.is_empty()
+    }
+    /// Number of invalid bytes.
+    pub fn count(&self) -> usize {
+        self.invalid.len()
+    }
+    /// Positions of the invalid bytes.
+    pub fn positions(&self) -> Vec<usize> {
+        self.invalid.iter().map(|(position, _)| *position).collect()
+    }
+    /// Positions of each invalid byte, keyed by byte.
+    pub fn by_byte(&self) -> BTreeMap<u8, Vec<usize>> {
+        let mut summary: BTreeMap<u8, Vec<usize>> = BTreeMap::new();
+        self.invalid.iter().for_each(|(position, u)| summary.entry(*u).or_default().push(*position));
+        summary
+    }
+    /// Ok if valid, otherwise the first invalid byte as an InvalidCharacter error.
+    pub fn into_result(self) -> Result<()> {
+        match self.invalid.first() {
+            None => Ok(()),
+            Some((position, byte)) => Err(BioUtilsError::InvalidCharacter { position: *position, byte: *byte }),
+        }
+    }
+}
+
+impl std::fmt::Display for CharsetValidation {
+    /// One entry per invalid byte in byte order, non-ASCII bytes in hex, e.g. "1 '.' at 4; 3 'X' at 10, 55, 91; 1 0xC3 at 7".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let summary = self.by_byte().iter().map(|(u, positions)| {
+            let joined = positions.iter().map(|p| p.to_string()).collect::<Vec<String>>().join(", ");
+            match u.is_ascii() {
+                true => format!("{} {:?} at {}", positions.len(), *u as char, joined),
+                false => format!("{} 0x{:02X} at {}", positions.len(), u, joined),
+            }
+        }).collect::<Vec<String>>();
+        write!(f, "{}", summary.join("; "))
+    }
+}
+
 pub trait CheckAsRefPartialEq<K>{
     /// Checks if the sequence and quality u8 vectors are the same length. Generally checks two u8 items for length against each other
     fn is_length_eq(&self, comparison: &K) -> bool;
@@ -332,6 +438,24 @@ mod tests {
     #[test]
     fn checking_slice() {
         let test = &[67,67,67,67];
-        assert!(test.is_all_charset(BioUtilsCharSet::Dna), true);
+        assert!(test.is_all_charset(BioUtilsCharSet::Dna));
+    }
+
+    #[test]
+    fn invalid_character_position() {
+        match b"ACXT".result_is_all_charset(BioUtilsCharSet::Dna) {
+            Err(BioUtilsError::InvalidCharacter { position, byte }) => assert_eq!((position, byte), (2, b'X')),
+            _ => panic!("Expected an invalid character"),
+        }
+    }
+
+    #[test]
+    fn validation_summary() {
+        let validation = b"ACGTX".validate(BioUtilsCharSet::Dna);
+        assert!(!validation.is_valid());
+        assert_eq!(validation.positions(), vec![4]);
+        assert_eq!(validation.to_string(), "1 'X' at 4");
+        assert_eq!("AXé.X".validate(BioUtilsCharSet::Dna).to_string(), "1 '.' at 4; 2 'X' at 1, 5; 1 0xA9 at 3; 1 0xC3 at 2");
+        assert!(b"ACGT".validate(BioUtilsCharSet::Dna).is_valid());
     }
 }
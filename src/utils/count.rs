@@ -12,6 +12,7 @@ use super::*;
 
 use crate::utils::arithmetic::*;
 use crate::utils::percent::*;
+use crate::utils::simd;
 
 pub trait CountAsRefSlice<T> {
 
@@ -21,6 +22,9 @@ pub trait CountAsRefSlice<T> {
     /// Returns the number of occurrences of the mode
     fn count_mode(&self) -> Option<u64>;
 
+    /// Returns the number of G and C bases, upper or lower case
+    fn count_gc(&self) -> usize;
+
 }
 
 impl<T> CountAsRefSlice<T> for T
@@ -40,6 +44,11 @@ where
     fn count_u8(&self, x: &u8) -> Result<usize> {
         Ok(self.as_ref().iter().filter(|&q| q==x).count())
     }
+
+    /// Returns the number of G and C bases, upper or lower case
+    fn count_gc(&self) -> usize {
+        simd::count_gc(self.as_ref())
+    }
 }
 
 pub trait CountAsMutSlice<T> {
@@ -114,7 +123,6 @@ T: Ord,
             Ordering::Greater => Ok(Ordering::Greater),
             Ordering::Equal => Ok(Ordering::Equal),
             Ordering::Less => Ok(Ordering::Less),
-            _ => bail!("Comparison error"),
         }
     }
 }
@@ -124,11 +132,11 @@ pub trait PercentAsRefOrdSlice<T> {
     fn percent_cmp(&self, value: &u8) -> Result<BTreeMap<Ordering, u64>>;
     /// Returns a boolean if the total percent of elements above the cutoff u8 is above the supplied percent
     fn is_percent_ge(&self, cutoff_value: &u8, cutoff_percent: &usize) -> Result<bool>;
-    /// Returns the total percent of elements above the cutoff
+    /// Returns the total percent of elements above the cutoff, 0 for an empty slice
     fn percent_ge(&self, cutoff_value: &u8) -> Result<usize>;
     /// Returns a boolean if the total percent of elements above the cutoff u8 is above the supplied percent
     fn is_percent_le(&self, cutoff_value: &u8, cutoff_percent: &usize) -> Result<bool>;
-    /// Returns the total percent of elements above the cutoff
+    /// Returns the total percent of elements above the cutoff, 0 for an empty slice
     fn percent_le(&self, cutoff_value: &u8) -> Result<usize>;
 }
 
@@ -152,9 +160,12 @@ T: Ord,
         } else { Ok(false) }
     }
 
-    /// Returns the total percent of elements greater than or equal to the cutoff
+    /// Returns the total percent of elements greater than or equal to the cutoff, 0 for an empty slice
     fn percent_ge(&self, cutoff_value: &u8) -> Result<usize> {
-        percent_usize(&usize::try_from(self.count_ge(cutoff_value))?, &usize::try_from(self.as_ref().len())?)
+        match self.as_ref().len() {
+            0 => Ok(0),
+            length => percent_usize(&usize::try_from(self.count_ge(cutoff_value))?, &length),
+        }
     }
     /// Get the total percent of elements above the cutoff u8 and return a boolean if total above supplied percent
     fn is_percent_le(&self, cutoff_value: &u8, cutoff_percent: &usize) -> Result<bool> {
@@ -163,9 +174,12 @@ T: Ord,
         } else { Ok(false) }
     }
 
-    /// Returns the total percent of elements greater than or equal to the cutoff
+    /// Returns the total percent of elements less than or equal to the cutoff, 0 for an empty slice
     fn percent_le(&self, cutoff_value: &u8) -> Result<usize> {
-        percent_usize(&usize::try_from(self.count_le(cutoff_value))?, &usize::try_from(self.as_ref().len())?)
+        match self.as_ref().len() {
+            0 => Ok(0),
+            length => percent_usize(&usize::try_from(self.count_le(cutoff_value))?, &length),
+        }
     }
 }
 
@@ -206,12 +220,105 @@ T: Ord,
     }
 }
 
-/// Phred score (q) to probability (p): q = -10log10(p), p = 10 ^(-q/10)
-pub fn phred_to_prob(phred: &u8) -> f64 {-10f64 * (*phred as f64).log10()}
-/// Probability (p) to phred score (q): q = -10log10(p), p = 10 ^(-q/10)
-pub fn prob_to_phred(prob: &f64)-> u8 {(10f64.powf(-prob / 10f64)) as u8}
+// Error probability of a quality byte in the encoding, Solexa scores are log-odds.
+fn quality_error_prob(byte: u8, encoding: QualityEncoding) -> Result<f64> {
+    if !encoding.range().contains(&usize::from(byte)) {
+        return Err(BioUtilsError::QualityOutOfRange { byte, encoding: encoding.name() })
+    }
+    match encoding {
+        QualityEncoding::Solexa => {
+            let odds = 10f64.powf(-(f64::from(byte) - f64::from(encoding.offset())) / 10f64);
+            Ok(odds / (1f64 + odds))
+        }
+        _ => Ok(phred_to_prob(&(byte - encoding.offset()))),
+    }
+}
+
+pub trait ErrorAsRefSlice<T> {
+    /// Returns the expected number of errors of a quality string in the encoding, the sum of the error probabilities of its scores.
+    fn expected_errors(&self, encoding: QualityEncoding) -> Result<f64>;
+    /// Returns the mean error probability of a quality string in the encoding, not the mean score, which overstates the quality of reads with a few bad bases.
+    fn mean_error_prob(&self, encoding: QualityEncoding) -> Result<f64>;
+    /// Checks if the expected number of errors of a quality string in the encoding is at most maxee, as usearch -fastq_maxee.
+    fn is_expected_errors_le(&self, encoding: QualityEncoding, maxee: &f64) -> Result<bool>;
+}
+
+impl<T> ErrorAsRefSlice<T> for T where
+T: AsRef<[u8]>,
+{
+    /// Returns the expected number of errors of a quality string in the encoding, the sum of the error probabilities of its scores.
+    fn expected_errors(&self, encoding: QualityEncoding) -> Result<f64> {
+        self.as_ref().iter().try_fold(0f64, |sum, byte| Ok(sum + quality_error_prob(*byte, encoding)?))
+    }
+    /// Returns the mean error probability of a quality string in the encoding, not the mean score, which overstates the quality of reads with a few bad bases.
+    fn mean_error_prob(&self, encoding: QualityEncoding) -> Result<f64> {
+        match self.as_ref().len() {
+            0 => Err(BioUtilsError::MissingCharacter),
+            len => Ok(self.expected_errors(encoding)? / len as f64),
+        }
+    }
+    /// Checks if the expected number of errors of a quality string in the encoding is at most maxee, as usearch -fastq_maxee.
+    fn is_expected_errors_le(&self, encoding: QualityEncoding, maxee: &f64) -> Result<bool> {
+        Ok(self.expected_errors(encoding)? <= *maxee)
+    }
+}
+
+/// Phred score (q) to error probability (p): p = 10^(-q/10). Scores 0-93 are looked up.
+pub fn phred_to_prob(phred: &u8) -> f64 {
+    match PHRED_ERROR_PROB.get(*phred as usize) {
+        Some(prob) => *prob,
+        None => 10f64.powf(-f64::from(*phred) / 10f64),
+    }
+}
+/// Error probability (p) to Phred score (q): q = -10log10(p). Round to get an integer score.
+pub fn prob_to_phred(prob: &f64) -> f64 {-10f64 * prob.log10()}
 
 // /// Calculate an alignment flag of user's option. This is just the sum of the flags, so we can add together the options "read paired" and "read mapped in proper pair"
 // pub fn alignment_flag(input_flags: Vec<&str>) -> u16 {
 //     input_flags.iter().map(|e| FLAG_HASHMAP.get(e).to_owned()).collect()?
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_phred_and_prob() {
+        assert_eq!(phred_to_prob(&20), 0.01);
+        assert!((prob_to_phred(&0.001) - 30f64).abs() < 1e-9);
+        for phred in 0..=100u8 {
+            assert!((prob_to_phred(&phred_to_prob(&phred)) - f64::from(phred)).abs() < 1e-9);
+        }
+        // Q10, Q20, Q30 and Q40
+        let qual = b"+5?I";
+        assert!((qual.expected_errors(QualityEncoding::Phred33).unwrap() - 0.1111).abs() < 1e-12);
+        assert!((qual.mean_error_prob(QualityEncoding::Phred33).unwrap() - 0.027775).abs() < 1e-12);
+        assert!(qual.is_expected_errors_le(QualityEncoding::Phred33, &0.5).unwrap());
+        assert!(!qual.is_expected_errors_le(QualityEncoding::Phred33, &0.1).unwrap());
+        assert!((b"JT^h".expected_errors(QualityEncoding::Phred64).unwrap() - 0.1111).abs() < 1e-12);
+        assert!(b"!".expected_errors(QualityEncoding::Phred64).is_err());
+        assert!(b"".mean_error_prob(QualityEncoding::Phred33).is_err());
+        // Solexa -5 is log-odds, p = 10^0.5 / (1 + 10^0.5)
+        assert!((b";".expected_errors(QualityEncoding::Solexa).unwrap() - 0.759747).abs() < 1e-6);
+    }
+
+    #[test]
+    fn expected_errors_of_raw_fastq_quality() {
+        // A raw Phred+33 string is Q40 per base, not Q73
+        let qual = b"IIII";
+        assert!((qual.expected_errors(QualityEncoding::Phred33).unwrap() - 0.0004).abs() < 1e-12);
+        assert!((qual.mean_error_prob(QualityEncoding::Sanger).unwrap() - 0.0001).abs() < 1e-12);
+        assert!(matches!(b"II I".expected_errors(QualityEncoding::Phred33), Err(BioUtilsError::QualityOutOfRange { byte: b' ', .. })));
+    }
+
+    #[test]
+    fn percents_of_empty_slices() {
+        let empty: [u8; 0] = [];
+        assert_eq!(empty.percent_ge(&20).unwrap(), 0);
+        assert_eq!(empty.percent_le(&20).unwrap(), 0);
+        assert!(empty.is_percent_ge(&20, &0).unwrap());
+        assert!(!empty.is_percent_ge(&20, &1).unwrap());
+        assert!(empty.percent_cmp(&20).unwrap().is_empty());
+        assert_eq!([10u8, 20, 30, 40].percent_ge(&20).unwrap(), 75);
+    }
+}
@@ -10,10 +10,40 @@
 //! // Returns all cpg sites in a vector
 //! let cgpos = b"ACGA".all_positions_cg();
 //! ```
+//! Open reading frames in all six frames, as 0-based half-open forward strand coordinates including the stop codon.
+//! ```
+//! use bioutils::charsets::codon::GeneticCode;
+//! use bioutils::utils::find::{OrfAsRef, OrfMode, Strand};
+//! let seq = b"CCATGAAAATGCCCTAGTT";
+//! let orfs = seq.find_orfs(GeneticCode::Standard, 9, OrfMode::Nested).unwrap();
+//! assert_eq!(orfs.iter().map(|orf| (orf.start, orf.end)).collect::<Vec<(usize, usize)>>(), vec![(2, 17), (8, 17)]);
+//! let longest = seq.find_orfs(GeneticCode::Standard, 9, OrfMode::Longest).unwrap();
+//! assert_eq!((longest.len(), longest[0].strand, longest[0].frame), (1, Strand::Forward, 3));
+//! ```
+//! Degenerate motifs with IUPAC codes on both strands, e.g. the m6A DRACH motif.
+//! ```
+//! use bioutils::utils::find::{Motif, MotifAsRef, MotifMatch, Strand};
+//! let drach = Motif::new(b"DRACH").unwrap();
+//! assert_eq!(drach.find_all(b"TTGGACTT"), vec![MotifMatch { position: 2, strand: Strand::Forward }]);
+//! assert_eq!(drach.find_all(b"AAGTCCAA"), vec![MotifMatch { position: 1, strand: Strand::Reverse }]);
+//! // Palindromic motifs such as GGNCC are only reported on the forward strand
+//! assert_eq!(b"AGGTCCA".find_motif(b"GGNCC").unwrap().len(), 1);
+//! ```
+//! Approximate matches with up to k edits (Myers' bit-vector algorithm) or k mismatches.
+//! ```
+//! use bioutils::utils::find::{ApproxAsRef, ApproxMatch};
+//! let read = b"TTTAGATCGGAGCAA";
+//! // AGATCGGAAGAGC with one base deleted in the read
+//! let hits = read.find_approx(b"AGATCGGAAG", 1).unwrap();
+//! assert!(hits.contains(&ApproxMatch { end: 12, distance: 1 }));
+//! assert_eq!(read.find_hamming(b"AGATCGCA", 1).unwrap(), vec![ApproxMatch { end: 11, distance: 1 }]);
+//! ```
 
 use super::*;
 use memchr::memmem;
 use memchr::memmem::FindIter;
+use crate::utils::check::AllAsRefSlice;
+use crate::utils::recode::BioUtilsAsRefDoubleEndedIterator;
 
 pub trait MemChrAsRef<T> {
     /// Returns all positions of a byte slice by memchr search.
@@ -63,6 +93,241 @@ where
     }
 }
 
+/// Strand of a feature relative to the given sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strand {
+    Forward,
+    Reverse,
+}
+
+/// Which starts are reported for each stop codon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrfMode {
+    /// Every in-frame start before the stop, so shorter ORFs nested in longer ones are reported too.
+    Nested,
+    /// Only the most upstream start before the stop.
+    Longest,
+}
+
+/// Open reading frame from a start codon to, and including, a stop codon. start and end are 0-based half-open on the forward strand, frame is 1, 2, 3 or -1, -2, -3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Orf {
+    pub start: usize,
+    pub end: usize,
+    pub strand: Strand,
+    pub frame: i8,
+}
+
+// ORFs in one frame of a forward or reverse complemented sequence, in that sequence's coordinates.
+fn frame_orfs(seq: &[u8], offset: usize, code: GeneticCode, min_len: usize, mode: OrfMode) -> Vec<(usize, usize)> {
+    let mut orfs = Vec::new();
+    let mut starts: Vec<usize> = Vec::new();
+    for (i, codon) in seq.get(offset..).unwrap_or_default().chunks_exact(3).enumerate() {
+        let position = offset + 3 * i;
+        let index = match GeneticCode::codon_index(codon) {
+            Some(index) => index,
+            None => continue,
+        };
+        if code.amino_acids()[index] == STOP {
+            orfs.extend(starts.drain(..).map(|start| (start, position + 3)).filter(|(start, end)| end - start >= min_len));
+        } else if code.starts()[index] == b'M' && (mode == OrfMode::Nested || starts.is_empty()) {
+            starts.push(position);
+        }
+    }
+    orfs
+}
+
+pub trait OrfAsRef<T> {
+    /// Finds ORFs of at least min_len bases, stop codon included, in all six frames. Bases are ACGTN of either case, and codons with an N are skipped. Returns an InvalidCharacter error for any other byte.
+    fn find_orfs(&self, code: GeneticCode, min_len: usize, mode: OrfMode) -> Result<Vec<Orf>>;
+}
+
+impl<T> OrfAsRef<T> for T
+where
+    T: AsRef<[u8]>,
+{
+    /// Finds ORFs of at least min_len bases, stop codon included, in all six frames. Bases are ACGTN of either case, and codons with an N are skipped. Returns an InvalidCharacter error for any other byte.
+    fn find_orfs(&self, code: GeneticCode, min_len: usize, mode: OrfMode) -> Result<Vec<Orf>> {
+        let seq = self.as_ref();
+        seq.result_is_all_charset(BioUtilsCharSet::DnanMixCase)?;
+        let rev = seq.rev_recode(BioUtilsRecodeSet::DnanComplementMixCase).ok_or(BioUtilsError::MissingCharacter)?;
+        let mut orfs = Vec::new();
+        for offset in 0..3 {
+            orfs.extend(frame_orfs(seq, offset, code, min_len, mode).into_iter().map(|(start, end)| Orf { start, end, strand: Strand::Forward, frame: offset as i8 + 1 }));
+        }
+        for offset in 0..3 {
+            orfs.extend(frame_orfs(&rev, offset, code, min_len, mode).into_iter().map(|(start, end)| Orf { start: seq.len() - end, end: seq.len() - start, strand: Strand::Reverse, frame: -(offset as i8) - 1 }));
+        }
+        Ok(orfs)
+    }
+}
+
+/// Motif match, position is the 0-based start on the forward strand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MotifMatch {
+    pub position: usize,
+    pub strand: Strand,
+}
+
+/// IUPAC motif of up to 64 bases compiled to shift-and bitmasks, so a scan is a shift, an or and an and per base on each strand.
+/// A sequence base matches if it is one of the bases of the motif code, e.g. R matches A and G, N matches any base, and an N in the sequence only matches N.
+#[derive(Debug, Clone)]
+pub struct Motif {
+    pattern: Vec<u8>,
+    forward: [u64; 256],
+    // None if the motif is its own reverse complement.
+    reverse: Option<[u64; 256]>,
+}
+
+impl Motif {
+    /// Compiles a motif of IUPAC nucleotide codes, either case. Returns an InvalidCharacter error for other bytes or gaps, and an InvalidPattern error if the motif is empty or longer than 64.
+    pub fn new(pattern: &[u8]) -> Result<Motif> {
+        if pattern.is_empty() || pattern.len() > 64 {
+            return Err(BioUtilsError::InvalidPattern(format!("Motif length {} is not within 1-64", pattern.len())))
+        }
+        let mut nibbles = Vec::with_capacity(pattern.len());
+        for (position, u) in pattern.iter().enumerate() {
+            match IupacNibble::from_byte(*u) {
+                Some(nibble) if !nibble.is_gap() => nibbles.push(nibble),
+                _ => return Err(BioUtilsError::InvalidCharacter { position, byte: *u }),
+            }
+        }
+        let rev = nibbles.iter().rev().map(|nibble| nibble.complement()).collect::<Vec<IupacNibble>>();
+        Ok(Motif {
+            pattern: nibbles.iter().map(|nibble| nibble.to_byte()).collect(),
+            forward: Motif::masks(&nibbles),
+            reverse: if rev == nibbles { None } else { Some(Motif::masks(&rev)) },
+        })
+    }
+
+    // Bit j of the mask of a byte is set if the byte matches motif position j.
+    fn masks(nibbles: &[IupacNibble]) -> [u64; 256] {
+        let mut masks = [0u64; 256];
+        for (byte, mask) in masks.iter_mut().enumerate() {
+            if let Some(base) = IupacNibble::from_byte(byte as u8).filter(|base| !base.is_gap()) {
+                nibbles.iter().enumerate().filter(|(_, code)| code.contains(base)).for_each(|(j, _)| *mask |= 1 << j);
+            }
+        }
+        masks
+    }
+
+    /// Uppercase motif.
+    pub fn pattern(&self) -> &[u8] {
+        &self.pattern
+    }
+
+    /// Checks if the motif is its own reverse complement, in which case matches are only reported on the forward strand.
+    pub fn is_palindromic(&self) -> bool {
+        self.reverse.is_none()
+    }
+
+    /// All matches on both strands, in order of position with forward before reverse. Matches may overlap.
+    pub fn find_all(&self, seq: &[u8]) -> Vec<MotifMatch> {
+        let len = self.pattern.len();
+        let hit = 1u64 << (len - 1);
+        let (mut forward, mut reverse) = (0u64, 0u64);
+        let mut matches = Vec::new();
+        for (i, u) in seq.iter().enumerate() {
+            forward = ((forward << 1) | 1) & self.forward[*u as usize];
+            if forward & hit != 0 {
+                matches.push(MotifMatch { position: i + 1 - len, strand: Strand::Forward });
+            }
+            if let Some(masks) = &self.reverse {
+                reverse = ((reverse << 1) | 1) & masks[*u as usize];
+                if reverse & hit != 0 {
+                    matches.push(MotifMatch { position: i + 1 - len, strand: Strand::Reverse });
+                }
+            }
+        }
+        matches
+    }
+
+    /// Number of matches on both strands.
+    pub fn count(&self, seq: &[u8]) -> usize {
+        self.find_all(seq).len()
+    }
+}
+
+pub trait MotifAsRef<T> {
+    /// Finds an IUPAC motif on both strands. Compile the motif once with Motif::new to scan many sequences.
+    fn find_motif(&self, pattern: &[u8]) -> Result<Vec<MotifMatch>>;
+}
+
+impl<T> MotifAsRef<T> for T
+where
+    T: AsRef<[u8]>,
+{
+    /// Finds an IUPAC motif on both strands. Compile the motif once with Motif::new to scan many sequences.
+    fn find_motif(&self, pattern: &[u8]) -> Result<Vec<MotifMatch>> {
+        Ok(Motif::new(pattern)?.find_all(self.as_ref()))
+    }
+}
+
+/// Approximate match, end is the 0-based exclusive end in the text and distance the number of edits or mismatches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ApproxMatch {
+    pub end: usize,
+    pub distance: usize,
+}
+
+pub trait ApproxAsRef<T> {
+    /// Finds every end position where the pattern matches with at most max_edits substitutions, insertions or deletions, using Myers' bit-vector algorithm. Bytes are compared exactly. Returns an InvalidPattern error if the pattern is empty or longer than 64.
+    fn find_approx(&self, pattern: &[u8], max_edits: usize) -> Result<Vec<ApproxMatch>>;
+    /// Finds every end position where the pattern matches with at most max_mismatches substitutions and no indels. Returns an InvalidPattern error if the pattern is empty.
+    fn find_hamming(&self, pattern: &[u8], max_mismatches: usize) -> Result<Vec<ApproxMatch>>;
+}
+
+impl<T> ApproxAsRef<T> for T
+where
+    T: AsRef<[u8]>,
+{
+    /// Finds every end position where the pattern matches with at most max_edits substitutions, insertions or deletions, using Myers' bit-vector algorithm. Bytes are compared exactly. Returns an InvalidPattern error if the pattern is empty or longer than 64.
+    fn find_approx(&self, pattern: &[u8], max_edits: usize) -> Result<Vec<ApproxMatch>> {
+        if pattern.is_empty() || pattern.len() > 64 {
+            return Err(BioUtilsError::InvalidPattern(format!("Pattern length {} is not within 1-64", pattern.len())))
+        }
+        let mut peq = [0u64; 256];
+        pattern.iter().enumerate().for_each(|(i, u)| peq[*u as usize] |= 1 << i);
+        let high = 1u64 << (pattern.len() - 1);
+        let (mut pv, mut mv, mut score) = (u64::MAX, 0u64, pattern.len());
+        let mut matches = Vec::new();
+        for (j, u) in self.as_ref().iter().enumerate() {
+            let eq = peq[*u as usize];
+            let xv = eq | mv;
+            let xh = ((eq & pv).wrapping_add(pv) ^ pv) | eq;
+            let mut ph = mv | !(xh | pv);
+            let mut mh = pv & xh;
+            if ph & high != 0 {
+                score += 1;
+            } else if mh & high != 0 {
+                score -= 1;
+            }
+            // No carry in from row 0, a match may start anywhere in the text.
+            ph <<= 1;
+            mh <<= 1;
+            pv = mh | !(xv | ph);
+            mv = ph & xv;
+            if score <= max_edits {
+                matches.push(ApproxMatch { end: j + 1, distance: score });
+            }
+        }
+        Ok(matches)
+    }
+    /// Finds every end position where the pattern matches with at most max_mismatches substitutions and no indels. Returns an InvalidPattern error if the pattern is empty.
+    fn find_hamming(&self, pattern: &[u8], max_mismatches: usize) -> Result<Vec<ApproxMatch>> {
+        if pattern.is_empty() {
+            return Err(BioUtilsError::InvalidPattern("Pattern is empty".to_string()))
+        }
+        Ok(self.as_ref().windows(pattern.len()).enumerate().filter_map(|(i, window)| {
+            let distance = window.iter().zip(pattern.iter()).filter(|(a, b)| a != b).count();
+            match distance <= max_mismatches {
+                true => Some(ApproxMatch { end: i + pattern.len(), distance }),
+                false => None,
+            }
+        }).collect())
+    }
+}
+
 /// Returns CG positions in the given &[u8]. Non short circuiting, will return all positions that have a 'CG'
 pub fn cg_positions(seq:&[u8])-> Result<Vec<usize>> {
     Ok(seq.windows(2).enumerate()
@@ -93,4 +358,79 @@ where
 //     fn find(&self, key: &K) -> Option<usize> {
 //         self.as_ref().iter().filter(|x| x == key)
 //     }
-// }
\ No newline at end of file
+// }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_orfs_on_both_strands() {
+        // ATG AAA TAG on the forward strand and CTA TTT CAT, the reverse complement of ATG AAA TAG
+        let seq = b"ATGAAATAGGGCTATTTCATG";
+        let orfs = seq.find_orfs(GeneticCode::Standard, 9, OrfMode::Nested).unwrap();
+        assert_eq!(orfs, vec![Orf { start: 0, end: 9, strand: Strand::Forward, frame: 1 }, Orf { start: 11, end: 20, strand: Strand::Reverse, frame: -2 }]);
+        assert!(seq.find_orfs(GeneticCode::Standard, 12, OrfMode::Nested).unwrap().is_empty());
+        assert!(b"ATGXAATAG".find_orfs(GeneticCode::Standard, 3, OrfMode::Nested).is_err());
+    }
+
+    #[test]
+    fn finds_orfs_with_n_and_lowercase() {
+        let expected = vec![Orf { start: 0, end: 9, strand: Strand::Forward, frame: 1 }, Orf { start: 11, end: 20, strand: Strand::Forward, frame: 3 }];
+        assert_eq!(b"ATGAAATAGNNATGCCCTAA".find_orfs(GeneticCode::Standard, 9, OrfMode::Nested).unwrap(), expected);
+        assert_eq!(b"ATGAAATAGNNatgccctaa".find_orfs(GeneticCode::Standard, 9, OrfMode::Nested).unwrap(), expected);
+        // The codon with an N is skipped and the ORF runs on to the stop
+        assert_eq!(b"atgNNNtag".find_orfs(GeneticCode::Standard, 9, OrfMode::Nested).unwrap(), vec![Orf { start: 0, end: 9, strand: Strand::Forward, frame: 1 }]);
+        let reverse = b"TTAGGGCATNNCTATTTCAT".find_orfs(GeneticCode::Standard, 9, OrfMode::Nested).unwrap();
+        assert!(reverse.iter().all(|orf| orf.strand == Strand::Reverse));
+        assert_eq!(reverse.len(), 2);
+    }
+
+    #[test]
+    fn finds_degenerate_motifs() {
+        let motif = Motif::new(b"rrach").unwrap();
+        let seq = b"GGACTNNACTAGTCCTgaact";
+        let naive = (0..=seq.len() - 5).filter(|i| {
+            seq[*i..*i + 5].iter().zip(motif.pattern()).all(|(u, p)| IupacNibble::from_byte(*p).unwrap().contains(IupacNibble::from_byte(*u).unwrap()))
+        }).collect::<Vec<usize>>();
+        let forward = motif.find_all(seq).iter().filter(|m| m.strand == Strand::Forward).map(|m| m.position).collect::<Vec<usize>>();
+        assert_eq!(forward, naive);
+        assert_eq!(forward, vec![0, 16]);
+        assert!(motif.find_all(seq).contains(&MotifMatch { position: 10, strand: Strand::Reverse }));
+        assert!(Motif::new(b"").is_err());
+        assert!(Motif::new(b"AC-T").is_err());
+        assert!(Motif::new(b"GAATTC").unwrap().is_palindromic());
+    }
+
+    // Semi-global edit distance of the pattern to a suffix of text[..end], for every end.
+    fn naive_approx(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+        let mut column = (0..=pattern.len()).collect::<Vec<usize>>();
+        let mut distances = Vec::new();
+        for u in text {
+            let mut next = vec![0; pattern.len() + 1];
+            for i in 1..=pattern.len() {
+                let cost = if pattern[i - 1] == *u { 0 } else { 1 };
+                next[i] = (column[i - 1] + cost).min(column[i] + 1).min(next[i - 1] + 1);
+            }
+            distances.push(next[pattern.len()]);
+            column = next;
+        }
+        distances
+    }
+
+    #[test]
+    fn approx_matches_dynamic_programming() {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        for _ in 0..50 {
+            let text = (0..200).map(|_| DNA[rng.gen_range(0..4)]).collect::<Vec<u8>>();
+            let pattern = (0..rng.gen_range(1..=64)).map(|_| DNA[rng.gen_range(0..4)]).collect::<Vec<u8>>();
+            let expected = naive_approx(&text, &pattern).into_iter().enumerate()
+                .filter(|(_, distance)| *distance <= 3)
+                .map(|(i, distance)| ApproxMatch { end: i + 1, distance }).collect::<Vec<ApproxMatch>>();
+            assert_eq!(text.find_approx(&pattern, 3).unwrap(), expected);
+        }
+        assert!(b"ACGT".find_approx(&[b'A'; 65], 1).is_err());
+        assert_eq!(b"ACGTACGT".find_hamming(b"ACCT", 1).unwrap(), vec![ApproxMatch { end: 4, distance: 1 }, ApproxMatch { end: 8, distance: 1 }]);
+    }
+}
@@ -0,0 +1,339 @@
+//! Cytosine methylation contexts (CG, CHG, CHH) on both strands, and CpG density and observed/expected ratios in windows. Either case is accepted, so soft-masked references work.
+//! # Examples
+//! ```
+//! use bioutils::utils::find::Strand;
+//! use bioutils::utils::methylation::*;
+//! let sites = b"ACGTCAGTCTTA".methylation_sites();
+//! assert_eq!(sites[0], MethylationSite { position: 1, strand: Strand::Forward, context: MethylationContext::Cg, trinucleotide: *b"CGT" });
+//! assert_eq!(sites[1], MethylationSite { position: 2, strand: Strand::Reverse, context: MethylationContext::Cg, trinucleotide: *b"CGT" });
+//! assert_eq!(sites[2].context, MethylationContext::Chg);
+//! assert_eq!(sites[4].trinucleotide, *b"CTT");
+//! // CpG counts in 4 base windows every 2 bases
+//! let windows = b"CGCGAATT".cpg_windows(4, 2);
+//! assert_eq!(windows.iter().map(|w| w.cpg).collect::<Vec<usize>>(), vec![2, 1, 0]);
+//! assert_eq!(windows[0].observed_expected(), 2.0);
+//! // CpG islands with the Gardiner-Garden and Frommer thresholds
+//! let seq = [b"AT".repeat(200), b"CGCA".repeat(100), b"AT".repeat(200)].concat();
+//! let islands = seq.cpg_islands(&CpgIslandParams::gardiner_garden());
+//! assert_eq!(islands.len(), 1);
+//! ```
+
+use super::*;
+use crate::utils::find::Strand;
+use crate::files::bed::{BedRecord, BedWriter};
+use crate::files::fasta::FastaReader;
+
+/// Context of a cytosine on its strand, H is A, C or T.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MethylationContext {
+    Cg,
+    Chg,
+    Chh,
+}
+
+impl MethylationContext {
+    /// Context as used by bisulfite aligners, e.g. CHG.
+    pub const fn value(&self) -> &'static str {
+        match *self {
+            MethylationContext::Cg => "CG",
+            MethylationContext::Chg => "CHG",
+            MethylationContext::Chh => "CHH",
+        }
+    }
+}
+
+/// Cytosine with a methylation context. position is 0-based on the forward strand, the C of a reverse strand site is a G on the forward strand. trinucleotide is the uppercase context read on the site's strand, N where a CG site runs past the end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MethylationSite {
+    pub position: usize,
+    pub strand: Strand,
+    pub context: MethylationContext,
+    pub trinucleotide: [u8; 3],
+}
+
+/// CpG, C and G counts of a window, 0-based half-open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CpgWindow {
+    pub start: usize,
+    pub end: usize,
+    pub c: usize,
+    pub g: usize,
+    pub cpg: usize,
+}
+
+impl CpgWindow {
+    /// Fraction of G and C bases.
+    pub fn gc_content(&self) -> f64 {
+        (self.c + self.g) as f64 / (self.end - self.start) as f64
+    }
+    /// CpG sites per base.
+    pub fn cpg_density(&self) -> f64 {
+        self.cpg as f64 / (self.end - self.start) as f64
+    }
+    /// Observed/expected CpG ratio, CpG * length / (C * G), as Gardiner-Garden and Frommer. 0 if there are no C or no G.
+    pub fn observed_expected(&self) -> f64 {
+        match self.c * self.g {
+            0 => 0.0,
+            cg => (self.cpg * (self.end - self.start)) as f64 / cg as f64,
+        }
+    }
+}
+
+// Complement of an uppercase base, N for anything else.
+const fn complement(base: u8) -> u8 {
+    match base {
+        b'A' => b'T',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'T' => b'A',
+        _ => b'N',
+    }
+}
+
+// Context from the two bases 3' of the C on its strand, already complemented for the reverse strand.
+const fn context(next: u8, after: u8) -> Option<MethylationContext> {
+    match (next, after) {
+        (b'G', _) => Some(MethylationContext::Cg),
+        (b'A' | b'C' | b'T', b'G') => Some(MethylationContext::Chg),
+        (b'A' | b'C' | b'T', b'A' | b'C' | b'T') => Some(MethylationContext::Chh),
+        _ => None,
+    }
+}
+
+/// Thresholds of a CpG island caller. Windows of window bases every step bases are kept if their GC content and observed/expected CpG ratio are at least min_gc and min_observed_expected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CpgIslandParams {
+    pub window: usize,
+    pub step: usize,
+    pub min_gc: f64,
+    pub min_observed_expected: f64,
+    pub min_length: usize,
+}
+
+impl CpgIslandParams {
+    /// Gardiner-Garden and Frommer (1987): 200 bases, GC 50%, observed/expected 0.6.
+    pub const fn gardiner_garden() -> CpgIslandParams {
+        CpgIslandParams { window: 200, step: 1, min_gc: 0.5, min_observed_expected: 0.6, min_length: 200 }
+    }
+    /// Takai and Jones (2002): 200 base windows, islands of at least 500 bases, GC 55%, observed/expected 0.65, which excludes most Alu repeats.
+    pub const fn takai_jones() -> CpgIslandParams {
+        CpgIslandParams { window: 200, step: 1, min_gc: 0.55, min_observed_expected: 0.65, min_length: 500 }
+    }
+    /// Checks if a window passes the GC and observed/expected thresholds.
+    pub fn passes(&self, window: &CpgWindow) -> bool {
+        window.gc_content() >= self.min_gc && window.observed_expected() >= self.min_observed_expected
+    }
+}
+
+impl Default for CpgIslandParams {
+    fn default() -> CpgIslandParams {
+        CpgIslandParams::gardiner_garden()
+    }
+}
+
+#[inline]
+fn is_cpg(seq: &[u8], i: usize) -> bool {
+    seq[i].eq_ignore_ascii_case(&b'C') && seq.get(i + 1).map_or(false, |u| u.eq_ignore_ascii_case(&b'G'))
+}
+
+// Counts of seq[start..end] by a single pass.
+fn count_window(seq: &[u8], start: usize, end: usize) -> CpgWindow {
+    let mut window = CpgWindow { start, end, c: 0, g: 0, cpg: 0 };
+    for i in start..end {
+        match seq[i].to_ascii_uppercase() {
+            b'C' => window.c += 1,
+            b'G' => window.g += 1,
+            _ => {}
+        }
+        window.cpg += (i + 1 < end && is_cpg(seq, i)) as usize;
+    }
+    window
+}
+
+// Rolls a window along the sequence one base at a time, so memory does not grow with the sequence, and calls f every step bases.
+fn for_each_window<F: FnMut(CpgWindow)>(seq: &[u8], window: usize, step: usize, mut f: F) {
+    if window == 0 || step == 0 || window > seq.len() {
+        return
+    }
+    let mut w = count_window(seq, 0, window);
+    f(w);
+    for start in 1..=seq.len() - window {
+        let (out, into) = (start - 1, start + window - 1);
+        match seq[out].to_ascii_uppercase() {
+            b'C' => w.c -= 1,
+            b'G' => w.g -= 1,
+            _ => {}
+        }
+        match seq[into].to_ascii_uppercase() {
+            b'C' => w.c += 1,
+            b'G' => w.g += 1,
+            _ => {}
+        }
+        if window > 1 {
+            w.cpg -= is_cpg(seq, out) as usize;
+            w.cpg += is_cpg(seq, into - 1) as usize;
+        }
+        w.start = start;
+        w.end = start + window;
+        if start % step == 0 {
+            f(w);
+        }
+    }
+}
+
+pub trait MethylationAsRef<T> {
+    /// All C on both strands with a CG, CHG or CHH context, in order of position. Contexts running into an N or past the end are skipped.
+    fn methylation_sites(&self) -> Vec<MethylationSite>;
+    /// Sites of one context on both strands.
+    fn methylation_sites_of(&self, context: MethylationContext) -> Vec<MethylationSite>;
+    /// CpG, C and G counts in windows of the given length every step bases. Only full windows are returned, none if the window or step is 0.
+    fn cpg_windows(&self, window: usize, step: usize) -> Vec<CpgWindow>;
+    /// CpG islands, merged runs of windows passing the GC and observed/expected thresholds. Each merged island must pass the thresholds as a whole and be at least min_length long.
+    fn cpg_islands(&self, params: &CpgIslandParams) -> Vec<CpgWindow>;
+    /// Observed/expected CpG ratio of the whole sequence.
+    fn cpg_observed_expected(&self) -> f64;
+}
+
+impl<T> MethylationAsRef<T> for T
+where
+    T: AsRef<[u8]>,
+{
+    /// All C on both strands with a CG, CHG or CHH context, in order of position. Contexts running into an N or past the end are skipped.
+    fn methylation_sites(&self) -> Vec<MethylationSite> {
+        let seq = self.as_ref();
+        let base = |i: usize| seq.get(i).map_or(b'N', |u| u.to_ascii_uppercase());
+        let mut sites = Vec::new();
+        for (i, u) in seq.iter().enumerate() {
+            match u.to_ascii_uppercase() {
+                b'C' => {
+                    if let Some(context) = context(base(i + 1), base(i + 2)) {
+                        sites.push(MethylationSite { position: i, strand: Strand::Forward, context, trinucleotide: [b'C', base(i + 1), base(i + 2)] });
+                    }
+                }
+                b'G' => {
+                    let (next, after) = match i {
+                        0 => (b'N', b'N'),
+                        1 => (complement(base(0)), b'N'),
+                        _ => (complement(base(i - 1)), complement(base(i - 2))),
+                    };
+                    if let Some(context) = context(next, after) {
+                        sites.push(MethylationSite { position: i, strand: Strand::Reverse, context, trinucleotide: [b'C', next, after] });
+                    }
+                }
+                _ => {}
+            }
+        }
+        sites
+    }
+    /// Sites of one context on both strands.
+    fn methylation_sites_of(&self, context: MethylationContext) -> Vec<MethylationSite> {
+        self.methylation_sites().into_iter().filter(|site| site.context == context).collect()
+    }
+    /// CpG, C and G counts in windows of the given length every step bases. Only full windows are returned, none if the window or step is 0.
+    fn cpg_windows(&self, window: usize, step: usize) -> Vec<CpgWindow> {
+        let mut windows = Vec::new();
+        for_each_window(self.as_ref(), window, step, |w| windows.push(w));
+        windows
+    }
+    /// CpG islands, merged runs of windows passing the GC and observed/expected thresholds. Each merged island must pass the thresholds as a whole and be at least min_length long.
+    fn cpg_islands(&self, params: &CpgIslandParams) -> Vec<CpgWindow> {
+        let seq = self.as_ref();
+        let mut islands = Vec::new();
+        let mut current: Option<(usize, usize)> = None;
+        let mut close = |start: usize, end: usize| {
+            let island = count_window(seq, start, end);
+            if end - start >= params.min_length && params.passes(&island) {
+                islands.push(island);
+            }
+        };
+        for_each_window(seq, params.window, params.step, |w| {
+            if !params.passes(&w) {
+                return
+            }
+            current = match current {
+                Some((start, end)) if w.start <= end => Some((start, w.end)),
+                Some((start, end)) => {
+                    close(start, end);
+                    Some((w.start, w.end))
+                }
+                None => Some((w.start, w.end)),
+            };
+        });
+        if let Some((start, end)) = current {
+            close(start, end);
+        }
+        islands
+    }
+    /// Observed/expected CpG ratio of the whole sequence.
+    fn cpg_observed_expected(&self) -> f64 {
+        count_window(self.as_ref(), 0, self.as_ref().len()).observed_expected()
+    }
+}
+
+/// CpG islands of every record in a FASTA reader as BED records named CpG:count, with the GC content and observed/expected ratio as extra columns.
+pub fn fasta_cpg_islands<R: Read>(reader: FastaReader<R>, params: &CpgIslandParams) -> Result<Vec<BedRecord>> {
+    let mut records = Vec::new();
+    for record in reader {
+        let record = record?;
+        let chrom = record.id()?.to_string();
+        records.extend(record.seq().cpg_islands(params).iter().map(|island| {
+            BedRecord::new(&chrom, island.start as u64, island.end as u64)
+                .with_name(&format!("CpG:{}", island.cpg))
+                .with_column(format!("{:.3}", island.gc_content()))
+                .with_column(format!("{:.3}", island.observed_expected()))
+        }));
+    }
+    Ok(records)
+}
+
+/// Calls CpG islands in a plain, gzip or BGZF FASTA file and writes them to a BED file. Returns the number of islands.
+pub fn cpg_islands_to_bed(fasta: &Path, bed: &Path, params: &CpgIslandParams) -> Result<usize> {
+    let islands = fasta_cpg_islands(FastaReader::from_path(fasta)?, params)?;
+    let mut writer = BedWriter::from_path(bed)?;
+    islands.iter().try_for_each(|island| writer.write_record(island))?;
+    writer.flush()?;
+    Ok(islands.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contexts_on_both_strands() {
+        // The G at 5 is a reverse strand C followed by T G, and the G at 7 one followed by T C.
+        let seq = b"CTTCAGAG";
+        let sites = seq.methylation_sites();
+        let summary = sites.iter().map(|s| (s.position, s.strand, s.context.value())).collect::<Vec<(usize, Strand, &str)>>();
+        assert_eq!(summary, vec![(0, Strand::Forward, "CHH"), (3, Strand::Forward, "CHG"), (5, Strand::Reverse, "CHG"), (7, Strand::Reverse, "CHH")]);
+        assert_eq!(b"acgn".methylation_sites_of(MethylationContext::Cg).len(), 2);
+        assert_eq!(b"CCGG".cpg_windows(2, 1).iter().map(|w| w.cpg).collect::<Vec<usize>>(), vec![0, 1, 0]);
+        assert!(b"CG".cpg_windows(3, 1).is_empty());
+        assert_eq!(b"CGCG".cpg_observed_expected(), 2.0);
+    }
+
+    #[test]
+    fn rolling_windows_match_counts() {
+        let seq = b"ACGCGTTAcgGGCATCGAATTCGCGCGAT";
+        for window in 1..6 {
+            let rolled = seq.cpg_windows(window, 1);
+            assert_eq!(rolled.len(), seq.len() - window + 1);
+            assert!(rolled.iter().all(|w| *w == count_window(seq, w.start, w.end)));
+        }
+        assert_eq!(seq.cpg_windows(5, 3).iter().map(|w| w.start).collect::<Vec<usize>>(), vec![0, 3, 6, 9, 12, 15, 18, 21, 24]);
+    }
+
+    #[test]
+    fn calls_islands() {
+        let island = b"CGCGGCATCGCGATCGGCGC".repeat(15);
+        let flank = b"ATTATAAATTTATATTAATA".repeat(20);
+        let seq = [&flank[..], &island[..], &flank[..]].concat();
+        let islands = seq.cpg_islands(&CpgIslandParams::gardiner_garden());
+        assert_eq!(islands.len(), 1);
+        assert!(islands[0].start <= 400 && islands[0].end >= 700);
+        assert!(seq.cpg_islands(&CpgIslandParams::takai_jones()).is_empty());
+        let fasta = [&b">chr1 test\n"[..], &seq[..], &b"\n"[..]].concat();
+        let bed = fasta_cpg_islands(FastaReader::new(&fasta[..]), &CpgIslandParams::default()).unwrap();
+        assert_eq!((bed[0].chrom.as_str(), bed[0].name.as_deref()), ("chr1", Some(format!("CpG:{}", islands[0].cpg).as_str())));
+    }
+}
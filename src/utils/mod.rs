@@ -13,4 +13,11 @@ pub mod percent;
 pub mod tryfrom;
 
 pub mod recode;
+pub mod simd;
+pub mod packed;
+pub mod translate;
+pub mod methylation;
+pub mod quality;
+pub mod trim;
+pub mod adapter;
 
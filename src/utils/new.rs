@@ -31,12 +31,12 @@ impl<T> RandomBioVec<T> for T
         for _base in 0..*nbases {
             vec.push(charset.value().choose(&mut rng).copied().to_owned())
         };
-        vec.into_iter().collect::<Option<Vec<u8>>>().ok_or(bail!("Cannot create new random"))
+        vec.into_iter().collect::<Option<Vec<u8>>>().ok_or(BioUtilsError::EmptyCharset)
     }
     /// Creates new u8 from the chosen characterset
     fn random_u8(charset: BioUtilsCharSet) -> Result<u8> {
         let mut rng = rand::thread_rng();
-        charset.value().choose(&mut rng).copied().ok_or(bail!("Cannot create new random"))
+        charset.value().choose(&mut rng).copied().ok_or(BioUtilsError::EmptyCharset)
     }
     /// Create new random sequences with specified number of elements.
     fn random_vec_with(nbases: &usize, charset: &[u8]) -> Result<Vec<u8>> {
@@ -45,12 +45,12 @@ impl<T> RandomBioVec<T> for T
         for _base in 0..*nbases {
             vec.push(charset.choose(&mut rng).copied().to_owned())
         };
-        vec.into_iter().collect::<Option<Vec<u8>>>().ok_or(bail!("Cannot create new random"))
+        vec.into_iter().collect::<Option<Vec<u8>>>().ok_or(BioUtilsError::EmptyCharset)
     }
     /// Creates new u8 from the chosen characterset
     fn random_u8_with(charset: &[u8]) -> Result<u8> {
         let mut rng = rand::thread_rng();
-        charset.choose(&mut rng).copied().ok_or(bail!("Cannot create new random"))
+        charset.choose(&mut rng).copied().ok_or(BioUtilsError::EmptyCharset)
     }
 }
 
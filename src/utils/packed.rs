@@ -0,0 +1,382 @@
+//! 2-bit packed DNA. ACGT are stored 32 bases per u64, and N bases are kept as sorted runs in a side table, so a reference takes a quarter of the memory of ASCII bytes.
+//! ```
+//! use bioutils::utils::packed::PackedDna;
+//! use bioutils::references::phix::PHIX_GENOME;
+//! let phix = PackedDna::from_slice(&PHIX_GENOME).unwrap();
+//! assert_eq!(phix.to_vec(), PHIX_GENOME.to_vec());
+//! let seq = PackedDna::from_slice(b"ACGTNNACGT").unwrap();
+//! assert_eq!(seq.rev_complement().to_vec(), b"ACGTNNACGT".to_vec());
+//! assert_eq!(seq.slice(2..6).unwrap().to_vec(), b"GTNN".to_vec());
+//! // 2-bit k-mers, skipping those that overlap an N
+//! let kmers = seq.kmers(3).map(|(position, kmer)| (position, PackedDna::decode_kmer(kmer, 3))).collect::<Vec<(usize, Vec<u8>)>>();
+//! assert_eq!(kmers, vec![(0, b"ACG".to_vec()), (1, b"CGT".to_vec()), (6, b"ACG".to_vec()), (7, b"CGT".to_vec())]);
+//! // 4-bit IUPAC codes, two per byte
+//! use bioutils::utils::packed::PackedIupac;
+//! let primer = PackedIupac::from_slice(b"ACRYN").unwrap();
+//! assert!(primer.matches_at(&PackedIupac::from_slice(b"TTACGTA").unwrap(), 2));
+//! assert_eq!(primer.rev_complement().to_vec(), b"NRYGT".to_vec());
+//! ```
+
+use super::*;
+use crate::utils::check::AllAsRefSlice;
+use std::ops::Range;
+
+const BASES: [u8; 4] = [b'A', b'C', b'G', b'T'];
+const BASES_PER_WORD: usize = 32;
+
+/// 2-bit code of an ACGT byte. N (and anything else) is 0, the N mask records where it was.
+const fn encode(base: u8) -> u64 {
+    match base {
+        b'C' => 1,
+        b'G' => 2,
+        b'T' => 3,
+        _ => 0,
+    }
+}
+
+/// DNA packed at 2 bits per base with a side table of N runs.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PackedDna {
+    bases: Vec<u64>,
+    n_runs: Vec<Range<usize>>,
+    len: usize,
+}
+
+impl PackedDna {
+    /// Creates an empty sequence.
+    pub fn new() -> PackedDna {
+        PackedDna::default()
+    }
+
+    /// Packs an ACGTN sequence. Returns an InvalidCharacter error for any other byte.
+    pub fn from_slice(seq: &[u8]) -> Result<PackedDna> {
+        seq.result_is_all_charset(BioUtilsCharSet::Dnan)?;
+        let mut packed = PackedDna { bases: Vec::with_capacity((seq.len() + BASES_PER_WORD - 1) / BASES_PER_WORD), n_runs: Vec::new(), len: 0 };
+        seq.iter().for_each(|u| packed.push_unchecked(*u));
+        Ok(packed)
+    }
+
+    /// Appends an ACGTN base. Returns an InvalidCharacter error for any other byte.
+    pub fn push(&mut self, base: u8) -> Result<()> {
+        if !BioUtilsCharSet::Dnan.table().contains(base) {
+            return Err(BioUtilsError::InvalidCharacter { position: self.len, byte: base })
+        }
+        self.push_unchecked(base);
+        Ok(())
+    }
+
+    fn push_unchecked(&mut self, base: u8) {
+        self.push_code(encode(base), base == b'N');
+    }
+
+    fn push_code(&mut self, code: u64, is_n: bool) {
+        if self.len % BASES_PER_WORD == 0 {
+            self.bases.push(0);
+        }
+        if let Some(word) = self.bases.last_mut() {
+            *word |= code << (2 * (self.len % BASES_PER_WORD));
+        }
+        if is_n {
+            match self.n_runs.last_mut() {
+                Some(run) if run.end == self.len => run.end += 1,
+                _ => self.n_runs.push(self.len..self.len + 1),
+            }
+        }
+        self.len += 1;
+    }
+
+    /// Number of bases.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// True if there are no bases.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Runs of N as half-open ranges, in order.
+    pub fn n_runs(&self) -> &[Range<usize>] {
+        &self.n_runs
+    }
+
+    /// 2-bit code (A 0, C 1, G 2, T 3) of the base at the position. N bases are 0, check is_n.
+    pub fn code(&self, position: usize) -> Option<u64> {
+        if position >= self.len {
+            return None
+        }
+        Some((self.bases[position / BASES_PER_WORD] >> (2 * (position % BASES_PER_WORD))) & 3)
+    }
+
+    /// Checks if the base at the position is N.
+    pub fn is_n(&self, position: usize) -> bool {
+        match self.n_runs.partition_point(|run| run.start <= position) {
+            0 => false,
+            i => self.n_runs[i - 1].end > position,
+        }
+    }
+
+    /// ASCII base at the position.
+    pub fn get(&self, position: usize) -> Option<u8> {
+        let code = self.code(position)?;
+        match self.is_n(position) {
+            true => Some(b'N'),
+            false => Some(BASES[code as usize]),
+        }
+    }
+
+    /// Copies the bases in the range into a new packed sequence, or None if the range is out of bounds.
+    pub fn slice(&self, range: Range<usize>) -> Option<PackedDna> {
+        if range.start > range.end || range.end > self.len {
+            return None
+        }
+        let mut packed = PackedDna::new();
+        for position in range {
+            packed.push_code(self.code(position)?, self.is_n(position));
+        }
+        Some(packed)
+    }
+
+    /// Reverse complement. N runs are mirrored and stay N.
+    pub fn rev_complement(&self) -> PackedDna {
+        let mut packed = PackedDna { bases: Vec::with_capacity(self.bases.len()), n_runs: Vec::new(), len: 0 };
+        for position in (0..self.len).rev() {
+            let code = (self.bases[position / BASES_PER_WORD] >> (2 * (position % BASES_PER_WORD))) & 3;
+            packed.push_code(code ^ 3, false);
+        }
+        packed.n_runs = self.n_runs.iter().rev().map(|run| self.len - run.end..self.len - run.start).collect();
+        packed
+    }
+
+    /// Iterator over the 2-bit encoded k-mers and their positions, skipping k-mers that overlap an N. The first base is in the highest bits. k must be 1-32.
+    pub fn kmers(&self, k: usize) -> PackedKmers<'_> {
+        PackedKmers { seq: self, k, position: 0, kmer: 0, valid: 0, n_run: 0 }
+    }
+
+    /// Decodes a 2-bit k-mer from kmers back to ASCII.
+    pub fn decode_kmer(kmer: u64, k: usize) -> Vec<u8> {
+        (0..k).rev().map(|i| BASES[((kmer >> (2 * i)) & 3) as usize]).collect()
+    }
+
+    /// Unpacks to ASCII bytes.
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut seq = (0..self.len).map(|position| BASES[((self.bases[position / BASES_PER_WORD] >> (2 * (position % BASES_PER_WORD))) & 3) as usize]).collect::<Vec<u8>>();
+        self.n_runs.iter().for_each(|run| seq[run.clone()].iter_mut().for_each(|u| *u = b'N'));
+        seq
+    }
+}
+
+impl TryFrom<&[u8]> for PackedDna {
+    type Error = BioUtilsError;
+    fn try_from(seq: &[u8]) -> Result<PackedDna> {
+        PackedDna::from_slice(seq)
+    }
+}
+
+impl TryFrom<Vec<u8>> for PackedDna {
+    type Error = BioUtilsError;
+    fn try_from(seq: Vec<u8>) -> Result<PackedDna> {
+        PackedDna::from_slice(&seq)
+    }
+}
+
+impl From<&PackedDna> for Vec<u8> {
+    fn from(packed: &PackedDna) -> Vec<u8> {
+        packed.to_vec()
+    }
+}
+
+impl From<PackedDna> for Vec<u8> {
+    fn from(packed: PackedDna) -> Vec<u8> {
+        packed.to_vec()
+    }
+}
+
+/// IUPAC nucleotides packed at 4 bits per base, two per byte. See charsets::nibble for the encoding.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PackedIupac {
+    nibbles: Vec<u8>,
+    len: usize,
+}
+
+impl PackedIupac {
+    /// Creates an empty sequence.
+    pub fn new() -> PackedIupac {
+        PackedIupac::default()
+    }
+
+    /// Packs an IUPAC_NUCLEOTIDE sequence of either case. Returns an InvalidCharacter error for any other byte.
+    pub fn from_slice(seq: &[u8]) -> Result<PackedIupac> {
+        let mut packed = PackedIupac { nibbles: Vec::with_capacity((seq.len() + 1) / 2), len: 0 };
+        for (position, u) in seq.iter().enumerate() {
+            match IupacNibble::from_byte(*u) {
+                Some(nibble) => packed.push_nibble(nibble),
+                None => return Err(BioUtilsError::InvalidCharacter { position, byte: *u }),
+            }
+        }
+        Ok(packed)
+    }
+
+    /// Appends a nibble.
+    pub fn push_nibble(&mut self, nibble: IupacNibble) {
+        if self.len % 2 == 0 {
+            self.nibbles.push(nibble.bits());
+        } else if let Some(byte) = self.nibbles.last_mut() {
+            *byte |= nibble.bits() << 4;
+        }
+        self.len += 1;
+    }
+
+    /// Number of bases.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// True if there are no bases.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Nibble at the position.
+    pub fn get(&self, position: usize) -> Option<IupacNibble> {
+        if position >= self.len {
+            return None
+        }
+        Some(IupacNibble::from_bits(self.nibbles[position / 2] >> (4 * (position % 2))))
+    }
+
+    /// Iterator over the nibbles.
+    pub fn iter(&self) -> impl Iterator<Item = IupacNibble> + '_ {
+        (0..self.len).map(move |position| IupacNibble::from_bits(self.nibbles[position / 2] >> (4 * (position % 2))))
+    }
+
+    /// Reverse complement, ambiguity codes are complemented as sets, e.g. R to Y.
+    pub fn rev_complement(&self) -> PackedIupac {
+        let mut packed = PackedIupac { nibbles: Vec::with_capacity(self.nibbles.len()), len: 0 };
+        let nibbles = self.iter().collect::<Vec<IupacNibble>>();
+        nibbles.iter().rev().for_each(|nibble| packed.push_nibble(nibble.complement()));
+        packed
+    }
+
+    /// Checks if every code shares a base with the target at the offset, e.g. a primer with ambiguity codes against a sequence.
+    pub fn matches_at(&self, target: &PackedIupac, offset: usize) -> bool {
+        offset + self.len <= target.len && self.iter().enumerate().all(|(i, nibble)| target.get(offset + i).map_or(false, |t| nibble.matches(t)))
+    }
+
+    /// Merges two sequences of the same length into their IUPAC consensus, e.g. A and G to R. None if the lengths differ.
+    pub fn union(&self, other: &PackedIupac) -> Option<PackedIupac> {
+        if self.len != other.len {
+            return None
+        }
+        Some(PackedIupac { nibbles: self.nibbles.iter().zip(other.nibbles.iter()).map(|(a, b)| a | b).collect(), len: self.len })
+    }
+
+    /// Unpacks to uppercase DNA IUPAC bytes, gaps as -.
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.iter().map(|nibble| nibble.to_byte()).collect()
+    }
+}
+
+impl TryFrom<&[u8]> for PackedIupac {
+    type Error = BioUtilsError;
+    fn try_from(seq: &[u8]) -> Result<PackedIupac> {
+        PackedIupac::from_slice(seq)
+    }
+}
+
+impl From<&PackedIupac> for Vec<u8> {
+    fn from(packed: &PackedIupac) -> Vec<u8> {
+        packed.to_vec()
+    }
+}
+
+impl From<&PackedDna> for PackedIupac {
+    fn from(packed: &PackedDna) -> PackedIupac {
+        let mut iupac = PackedIupac { nibbles: Vec::with_capacity((packed.len + 1) / 2), len: 0 };
+        for position in 0..packed.len {
+            match packed.is_n(position) {
+                true => iupac.push_nibble(IupacNibble::from_bits(0b1111)),
+                false => iupac.push_nibble(IupacNibble::from_bits(1 << ((packed.bases[position / BASES_PER_WORD] >> (2 * (position % BASES_PER_WORD))) & 3))),
+            }
+        }
+        iupac
+    }
+}
+
+/// Rolling 2-bit k-mer iterator over a packed sequence, returned by PackedDna::kmers.
+pub struct PackedKmers<'a> {
+    seq: &'a PackedDna,
+    k: usize,
+    position: usize,
+    kmer: u64,
+    // Number of consecutive non-N bases ending at the current position.
+    valid: usize,
+    // Index of the next N run that can overlap the current position.
+    n_run: usize,
+}
+
+impl<'a> Iterator for PackedKmers<'a> {
+    type Item = (usize, u64);
+
+    fn next(&mut self) -> Option<(usize, u64)> {
+        if self.k == 0 || self.k > 32 {
+            return None
+        }
+        let mask = if self.k == 32 { u64::MAX } else { (1 << (2 * self.k)) - 1 };
+        while self.position < self.seq.len {
+            let position = self.position;
+            self.position += 1;
+            while self.n_run < self.seq.n_runs.len() && self.seq.n_runs[self.n_run].end <= position {
+                self.n_run += 1;
+            }
+            if self.seq.n_runs.get(self.n_run).map_or(false, |run| run.start <= position) {
+                self.valid = 0;
+                continue
+            }
+            let code = (self.seq.bases[position / BASES_PER_WORD] >> (2 * (position % BASES_PER_WORD))) & 3;
+            self.kmer = ((self.kmer << 2) | code) & mask;
+            self.valid += 1;
+            if self.valid >= self.k {
+                return Some((position + 1 - self.k, self.kmer))
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::recode::BioUtilsAsRefDoubleEndedIterator;
+
+    #[test]
+    fn packs_and_unpacks() {
+        let seq = b"NNACGTTGCANNNACGTACGTACGTACGTACGTACGTGGN".to_vec();
+        let packed = PackedDna::from_slice(&seq).unwrap();
+        assert_eq!(packed.len(), seq.len());
+        assert_eq!(packed.to_vec(), seq);
+        assert_eq!(packed.n_runs(), &[0..2, 10..13, 39..40]);
+        assert_eq!(packed.get(11), Some(b'N'));
+        assert_eq!(packed.rev_complement().to_vec(), seq.rev_recode(BioUtilsRecodeSet::DnanComplement).unwrap());
+        assert_eq!(packed.slice(9..14).unwrap().to_vec(), b"ANNNA".to_vec());
+        assert!(packed.slice(30..50).is_none());
+        let kmers = packed.kmers(4).map(|(position, _)| position).collect::<Vec<usize>>();
+        assert_eq!(kmers.first(), Some(&2));
+        assert!(kmers.iter().all(|p| (*p..*p + 4).all(|i| !packed.is_n(i))));
+        assert!(PackedDna::from_slice(b"ACGX").is_err());
+        assert_eq!(PackedIupac::from(&packed).to_vec(), seq);
+    }
+
+    #[test]
+    fn packs_iupac() {
+        let packed = PackedIupac::from_slice(b"acgtRYSWKMBDHVN-u").unwrap();
+        assert_eq!(packed.to_vec(), b"ACGTRYSWKMBDHVN-T".to_vec());
+        assert_eq!(packed.rev_complement().to_vec(), b"A-NBDHVKMWSRYACGT".to_vec());
+        let a = PackedIupac::from_slice(b"ACGT").unwrap();
+        let g = PackedIupac::from_slice(b"GCTT").unwrap();
+        assert_eq!(a.union(&g).unwrap().to_vec(), b"RCKT".to_vec());
+        assert!(PackedIupac::from_slice(b"RCKT").unwrap().matches_at(&a, 0));
+        assert!(!a.matches_at(&g, 0));
+        assert!(PackedIupac::from_slice(b"ACQ").is_err());
+    }
+}
@@ -1,8 +1,19 @@
 //! Calculates percents and checks whether the percent is 0-100.
-//! Intakes self as u8, with target use each element of a slice
+//! Intakes self as u8, with target use each element of a slice. Errors if the denominator is zero or the percent is above 100.
 
 use super::*;
-use super::tryfrom::*;
+
+// Rounded percent, computed in u128 so 100 * numerator cannot overflow. Errors on a zero denominator or a percent above 100.
+fn checked_percent(numerator: u128, denominator: u128) -> Result<u64> {
+    if denominator == 0 {
+        return Err(BioUtilsError::ZeroDenominator)
+    }
+    let percent = (100 * numerator + denominator / 2) / denominator;
+    match percent <= PERCENT_MAX_U64 as u128 {
+        true => Ok(percent as u64),
+        false => Err(BioUtilsError::PercentOutOfRange(u64::try_from(percent).unwrap_or(u64::MAX))),
+    }
+}
 
 /// Intakes self as u8 and denominator as u8, returns percent as a u8. 
 pub trait PercentAsRefU8 {
@@ -14,11 +25,8 @@ impl PercentAsRefU8 for u8
 {
     /// Returns the percent of u8s as u8
     fn percent_u8(&self, denominator: &u8) -> Result<u8> {
-    let percent = (100 * (*self) + denominator / 2) / denominator;
-    match PercentU8::try_from(percent) {
-        Ok(_) => Ok(percent),
-        Err(_) => bail!("Percent not valid or within the range 0-100"),
-    }}
+    checked_percent(u128::from(*self), u128::from(*denominator)).map(|percent| percent as u8)
+    }
 }
 
 /// Intakes self as u8 and denominator as usize, returns percent as a usize. 
@@ -31,11 +39,8 @@ impl PercentAsRefUsize for u8
 {
     /// Returns the percent of u8 and usize as usize
     fn percent_usize(&self, denominator: &usize) -> Result<usize> {
-    let percent = (100 * usize::try_from(*self)? + denominator / 2) / denominator;
-    match PercentUsize::try_from(percent) {
-        Ok(_) => Ok(percent as usize),
-        Err(_) => bail!("Percent not valid or within the range 0-100"),
-    }}
+    checked_percent(u128::from(*self), *denominator as u128).map(|percent| percent as usize)
+    }
 }
 
 /// Intakes self as u8 and denominator as u64, returns percent as a u64.
@@ -48,48 +53,46 @@ impl PercentAsRefU64 for u8
 {
     /// Returns the percent of u8 and u64 as u64
     fn percent_u64(&self, denominator: &u64) -> Result<u64> {
-    let percent = (100 * u64::from(*self) + denominator / 2) / denominator;
-    match PercentU64::try_from(percent) {
-        Ok(_) => Ok(percent as u64),
-        Err(_) => bail!("Percent not valid or within the range 0-100"),
-    }}
+    checked_percent(u128::from(*self), u128::from(*denominator))
+    }
 }
 
 impl PercentAsRefU64 for u64
 {
     /// Returns the percent of u64 and u64 as u64
     fn percent_u64(&self, denominator: &u64) -> Result<u64> {
-    let percent = (100 * self + denominator / 2) / denominator;
-    match PercentU64::try_from(percent) {
-        Ok(_) => Ok(percent as u64),
-        Err(_) => bail!("Percent not valid or within the range 0-100"),
-    }}
+    checked_percent(u128::from(*self), u128::from(*denominator))
+    }
 }
 
-/// Calculates a percent with usize numerator and denominator
+/// Calculates a percent with u8 numerator and denominator
 pub fn percent_u8(numerator: &u8, denominator: &u8) -> Result<u8> {
-    let percent = (100 * numerator + denominator / 2) / denominator;
-    match PercentU8::try_from(percent) {
-        Ok(_) => Ok(percent),
-        Err(_) => bail!("Percent not valid or within the range 0-100"),
-    }
+    checked_percent(u128::from(*numerator), u128::from(*denominator)).map(|percent| percent as u8)
 }
 
 /// Calculates a percent with usize numerator and denominator
 pub fn percent_usize(numerator: &usize, denominator: &usize) -> Result<usize> {
-    let percent = (100 * numerator + denominator / 2) / denominator;
-    match PercentUsize::try_from(percent) {
-        Ok(_) => Ok(percent),
-        Err(_) => bail!("Percent not valid or within the range 0-100"),
-    }
+    checked_percent(*numerator as u128, *denominator as u128).map(|percent| percent as usize)
 }
 
 /// Calculates a percent with u64 numerator and denominator
 pub fn percent_u64(numerator: &u64, denominator: &u64) -> Result<u64> {
-    let percent = (100 * numerator + denominator / 2) / denominator;
-    match PercentU64::try_from(percent) {
-        Ok(_) => Ok(percent),
-        Err(_) => bail!("Percent not valid or within the range 0-100"),
-    }
+    checked_percent(u128::from(*numerator), u128::from(*denominator))
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_denominator_and_range() {
+        assert_eq!(percent_u8(&3, &4).unwrap(), 75);
+        assert_eq!(200u8.percent_u8(&250).unwrap(), 80);
+        assert_eq!(percent_usize(&usize::MAX, &usize::MAX).unwrap(), 100);
+        assert!(matches!(percent_u8(&1, &0), Err(BioUtilsError::ZeroDenominator)));
+        assert!(matches!(percent_usize(&0, &0), Err(BioUtilsError::ZeroDenominator)));
+        assert!(matches!(7u64.percent_u64(&0), Err(BioUtilsError::ZeroDenominator)));
+        assert!(matches!(percent_u64(&3, &2), Err(BioUtilsError::PercentOutOfRange(150))));
+    }
+}
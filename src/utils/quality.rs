@@ -0,0 +1,203 @@
+//! Infer the encoding of FASTQ quality strings from the lowest and highest ASCII values seen. Bytes below ';' (59) can only be Phred+33, and bytes above 'K' (75) can only be +64 or long read Phred+33. When the observed range fits several encodings the detection is marked as ambiguous.
+//! # Examples
+//! ```
+//! use bioutils::charsets::quality::QualityEncoding;
+//! use bioutils::utils::quality::*;
+//! let detection = b"II##5?".detect_quality_encoding().unwrap();
+//! assert_eq!(detection.encoding, QualityEncoding::Phred33);
+//! assert_eq!(detection.confidence, QualityConfidence::Certain);
+//! // Illumina 1.3-1.7, could also be Solexa without any negative scores
+//! let mut detector = QualityDetector::new();
+//! detector.add(b"hhhhBBB");
+//! detector.add(b"hhh`aaT");
+//! let detection = detector.detect().unwrap();
+//! assert_eq!(detection.encoding, QualityEncoding::Phred64);
+//! assert_eq!(detection.confidence, QualityConfidence::Likely);
+//! assert_eq!(detection.candidates, vec![QualityEncoding::Sanger, QualityEncoding::Solexa, QualityEncoding::Phred64]);
+//! // Convert legacy qualities to Phred+33 in place, Solexa scores below 10 are log-odds
+//! let mut qual = b";@Jh".to_vec();
+//! qual.mut_convert_quality(QualityEncoding::Solexa, QualityEncoding::Phred33).unwrap();
+//! assert_eq!(qual, b"\"$+I".to_vec());
+//! ```
+
+use super::*;
+
+/// How well the observed quality range singles out an encoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum QualityConfidence {
+    /// Several encodings fit and none is favoured, e.g. only bytes 64-75 were seen.
+    Ambiguous,
+    /// Several encodings fit, but the range is typical of the chosen one.
+    Likely,
+    /// Only one encoding fits.
+    Certain,
+}
+
+/// Result of quality encoding detection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QualityDetection {
+    /// Most likely encoding.
+    pub encoding: QualityEncoding,
+    pub confidence: QualityConfidence,
+    /// Every encoding the observed range fits, Phred+33 first.
+    pub candidates: Vec<QualityEncoding>,
+    /// Lowest ASCII value seen.
+    pub min: u8,
+    /// Highest ASCII value seen.
+    pub max: u8,
+    /// Number of quality strings seen.
+    pub records: usize,
+}
+
+impl QualityDetection {
+    /// True if more than one encoding fits the observed range.
+    pub fn is_ambiguous(&self) -> bool {
+        self.candidates.len() > 1
+    }
+}
+
+/// Collects the ASCII range of quality strings, e.g. of the first records of a FASTQ file, and detects their encoding.
+#[derive(Debug, Clone)]
+pub struct QualityDetector {
+    min: u8,
+    max: u8,
+    records: usize,
+}
+
+impl QualityDetector {
+    /// Creates an empty detector.
+    pub fn new() -> QualityDetector {
+        QualityDetector { min: u8::MAX, max: u8::MIN, records: 0 }
+    }
+    /// Adds a quality string.
+    pub fn add(&mut self, qual: &[u8]) {
+        if let (Some(min), Some(max)) = (qual.iter().min(), qual.iter().max()) {
+            self.min = self.min.min(*min);
+            self.max = self.max.max(*max);
+        }
+        self.records += 1;
+    }
+    /// Number of quality strings added.
+    pub fn records(&self) -> usize {
+        self.records
+    }
+    /// Detects the encoding from the range seen so far. Errors if nothing was seen or a byte is outside printable ASCII 33-126.
+    pub fn detect(&self) -> Result<QualityDetection> {
+        if self.min > self.max {
+            return Err(BioUtilsError::MissingCharacter)
+        }
+        for byte in [self.min, self.max].iter() {
+            if !SANGER_RANGE.contains(&usize::from(*byte)) {
+                return Err(BioUtilsError::QualityOutOfRange { byte: *byte, encoding: QualityEncoding::Sanger.name() })
+            }
+        }
+        let phred33 = match usize::from(self.max) <= PHRED33_MAX_USIZE {
+            true => QualityEncoding::Phred33,
+            false => QualityEncoding::Sanger,
+        };
+        let candidates: Vec<QualityEncoding> = [phred33, QualityEncoding::Solexa, QualityEncoding::Phred64].iter()
+            .filter(|encoding| encoding.range().contains(&usize::from(self.min)))
+            .copied()
+            .collect();
+        let (encoding, confidence) = match (candidates.len(), phred33) {
+            (1, _) => (phred33, QualityConfidence::Certain),
+            // Bytes up to 'K' only, modern Phred+33 with high scores or +64 with scores of at most 11
+            (_, QualityEncoding::Phred33) => (phred33, QualityConfidence::Ambiguous),
+            // Long reads rarely have no score below 26, so prefer the +64 encodings
+            _ => match usize::from(self.min) >= PHRED64_MIN_USIZE {
+                true => (QualityEncoding::Phred64, QualityConfidence::Likely),
+                false => (QualityEncoding::Solexa, QualityConfidence::Likely),
+            },
+        };
+        Ok(QualityDetection { encoding, confidence, candidates, min: self.min, max: self.max, records: self.records })
+    }
+}
+
+impl Default for QualityDetector {
+    fn default() -> QualityDetector {
+        QualityDetector::new()
+    }
+}
+
+pub trait QualityAsRefSlice<T> {
+    /// Detects the encoding of a single quality string.
+    fn detect_quality_encoding(&self) -> Result<QualityDetection>;
+}
+
+impl<T> QualityAsRefSlice<T> for T where
+T: AsRef<[u8]>,
+{
+    /// Detects the encoding of a single quality string.
+    fn detect_quality_encoding(&self) -> Result<QualityDetection> {
+        let mut detector = QualityDetector::new();
+        detector.add(self.as_ref());
+        detector.detect()
+    }
+}
+
+pub trait QualityAsMutSlice<T> {
+    /// Converts a quality string from one encoding to another in place. Errors, leaving self unchanged, if a byte is not in the from encoding or the conversion is not supported.
+    fn mut_convert_quality(&mut self, from: QualityEncoding, to: QualityEncoding) -> Result<&mut Self>;
+}
+
+impl<T> QualityAsMutSlice<T> for T where
+T: AsMut<[u8]>,
+{
+    /// Converts a quality string from one encoding to another in place. Errors, leaving self unchanged, if a byte is not in the from encoding or the conversion is not supported.
+    fn mut_convert_quality(&mut self, from: QualityEncoding, to: QualityEncoding) -> Result<&mut Self> {
+        if let Some(byte) = self.as_mut().iter().find(|u| !from.range().contains(&usize::from(**u))) {
+            return Err(BioUtilsError::QualityOutOfRange { byte: *byte, encoding: from.name() })
+        }
+        if from == to {
+            return Ok(self)
+        }
+        match from.recode_to(to) {
+            Some(code) => {
+                let recode = code.recode_table();
+                self.as_mut().iter_mut().for_each(|u| *u = recode[*u as usize]);
+                Ok(self)
+            }
+            None => Err(BioUtilsError::UnsupportedQualityConversion { from: from.name(), to: to.name() }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::recode::{BioUtilsRecodeAsMutSlice, BioUtilsRecodeAsRefSlice};
+
+    #[test]
+    fn detects_encodings() {
+        let solexa = b";;<hhh".detect_quality_encoding().unwrap();
+        assert_eq!((solexa.encoding, solexa.confidence), (QualityEncoding::Solexa, QualityConfidence::Likely));
+        let ambiguous = b"@@EEJJ".detect_quality_encoding().unwrap();
+        assert_eq!((ambiguous.encoding, ambiguous.confidence), (QualityEncoding::Phred33, QualityConfidence::Ambiguous));
+        assert!(ambiguous.is_ambiguous());
+        let nanopore = b"##+~".detect_quality_encoding().unwrap();
+        assert_eq!((nanopore.encoding, nanopore.confidence), (QualityEncoding::Sanger, QualityConfidence::Certain));
+        let solexa_or_phred33 = b";;IJ".detect_quality_encoding().unwrap();
+        assert_eq!(solexa_or_phred33.candidates, vec![QualityEncoding::Phred33, QualityEncoding::Solexa]);
+        assert!(b" II".detect_quality_encoding().is_err());
+        assert!(QualityDetector::new().detect().is_err());
+    }
+
+    #[test]
+    fn converts_qualities() {
+        for (i, phred) in SOLEXA_TO_PHRED_SCORE.iter().enumerate() {
+            assert_eq!(*phred, solexa_to_phred(i as f64 - 5f64).round() as u8);
+        }
+        assert!((phred_to_solexa(solexa_to_phred(-3f64)) + 3f64).abs() < 1e-9);
+        let mut qual = b"@Jh~".to_vec();
+        qual.mut_convert_quality(QualityEncoding::Phred64, QualityEncoding::Phred33).unwrap();
+        assert_eq!(qual, b"!+IK".to_vec());
+        qual.mut_convert_quality(QualityEncoding::Phred33, QualityEncoding::Phred64).unwrap();
+        assert_eq!(qual, b"@Jhj".to_vec());
+        assert!(qual.mut_convert_quality(QualityEncoding::Phred33, QualityEncoding::Phred64).is_err());
+        assert_eq!(qual, b"@Jhj".to_vec());
+        assert!(qual.mut_convert_quality(QualityEncoding::Phred64, QualityEncoding::Solexa).is_err());
+        let mut solexa = b";".to_vec();
+        assert_eq!(solexa.recode(BioUtilsRecodeSet::Solexa64ToPhred33).unwrap(), b"\"".to_vec());
+        assert!(solexa.mut_recode(BioUtilsRecodeSet::Phred64ToPhred33).is_none());
+    }
+}
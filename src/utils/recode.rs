@@ -4,6 +4,7 @@
 //! use crate::bioutils::utils::recode::BioUtilsRecodeU8;
 //! use crate::bioutils::utils::recode::BioUtilsRecodeAsMutSlice;
 //! use crate::bioutils::utils::recode::BioUtilsAsMutDoubleEndedIterator;
+//! use crate::bioutils::utils::recode::BioUtilsAsRefDoubleEndedIterator;
 //! use bioutils::charsets::bioutils::*;
 //! 
 //! // Get the reverse complement of the sequence. Other options for finding the complement are available.
@@ -22,10 +23,40 @@
 //! let mut phred33_score_2 = b"00000".to_owned();
 //! phred33_score_2.mut_recode(BioUtilsRecodeSet::Phred33Encode);
 //! println!("{:?}", phred33_score_2);
+//!
+//! // Reverse complement of RNA in one call
+//! assert_eq!(b"AUGGC".rev_recode(BioUtilsRecodeSet::RnaComplement).unwrap(), b"GCCAU".to_vec());
+//!
+//! // In-silico bisulfite conversion of the top strand, keeping methylated CpG
+//! let mut reference = b"TCGACCA".to_owned();
+//! reference.mut_recode(BioUtilsRecodeSet::BisulfiteTopCpg);
+//! assert_eq!(&reference, b"TCGATTA");
 //! ```
 
 use super::*;
-use crate::utils::check::AllAsRefSlice;
+use crate::utils::simd;
+
+// Recodes in place. Context-dependent sets only change bases (C or G) that are never the context of another base (G or C), so a single pass sees the original neighbours.
+fn recode_in_place(seq: &mut [u8], code: &BioUtilsRecodeSet) {
+    let recode = code.recode_table();
+    if code.is_context_dependent() {
+        for i in 0..seq.len() {
+            if !code.is_protected(seq, i) {
+                seq[i] = recode[seq[i] as usize];
+            }
+        }
+    } else {
+        seq.iter_mut().for_each(|u| *u = recode[*u as usize]);
+    }
+}
+
+fn recoded(seq: &[u8], code: &BioUtilsRecodeSet) -> Vec<u8> {
+    let recode = code.recode_table();
+    seq.iter().enumerate().map(|(i, u)| match code.is_protected(seq, i) {
+        true => *u,
+        false => recode[*u as usize],
+    }).collect()
+}
 
 pub trait BioUtilsRecodeU8 {
     /// Checks if self can be recoded (recoding contains all u8 in self) and recodes self.
@@ -38,8 +69,8 @@ impl BioUtilsRecodeU8 for u8
 {
     /// Checks if self can be recoded (recoding contains all u8 in self) and recodes self.
     fn recode_u8(&self, code: BioUtilsRecodeSet) -> Option<u8> {
-        if code.value().charset.contains(self) {
-            Some(*code.value().recode.get(*self as usize)?)
+        if code.charset_table().contains(*self) {
+            Some(code.recode_table()[*self as usize])
         } else {
             None
         }
@@ -47,8 +78,8 @@ impl BioUtilsRecodeU8 for u8
     
     /// Checks if self can be recoded (recoding contains all u8 in self) and recodes self.
     fn mut_recode_u8(&mut self, code: BioUtilsRecodeSet) -> Option<&mut Self> {
-        if code.value().charset.contains(self) {
-            *self = *code.value().recode.get(*self as usize)?;
+        if code.charset_table().contains(*self) {
+            *self = code.recode_table()[*self as usize];
             Some(self)
         } else {
             None
@@ -66,9 +97,9 @@ T: AsMut<[u8]>,
 {
     /// Checks if self can be recoded (recoding contains all u8 in self) and recodes self.
     fn mut_recode(&mut self, code: BioUtilsRecodeSet) -> Option<&mut Self> {
-        let mut target = self.as_mut().iter_mut();
-        if target.all(|u| code.value().charset.contains(u)) {
-            target.for_each(|u| *u = code.value().recode[*u as usize]);
+        let charset = code.charset_table();
+        if self.as_mut().iter().all(|u| charset.contains(*u)) {
+            recode_in_place(self.as_mut(), &code);
             Some(self)
         } else {
             None
@@ -86,8 +117,9 @@ T: AsRef<[u8]>,
 {
     /// Checks if self can be recoded (recoding contains all u8 in self) and recodes self.
     fn recode(&self, code: BioUtilsRecodeSet) -> Option<Vec<u8>> {
-        if self.is_all_charset_with(code.value().charset) {
-            Some(self.as_ref().iter().map(|u| code.value().recode[*u as usize]).collect::<Vec<u8>>())
+        let charset = code.charset_table();
+        if self.as_ref().iter().all(|u| charset.contains(*u)) {
+            Some(recoded(self.as_ref(), &code))
         } else {
             None
         }
@@ -104,9 +136,18 @@ T: AsMut<[u8]>,
 {
     /// Recode the reverse of self, commonly for generating the reverse complement.
     fn mut_rev_recode(&mut self, code: BioUtilsRecodeSet) -> Option<&mut Self> {
-        let mut target = self.as_mut().iter_mut().rev();
-        if target.all(|u| code.value().charset.contains(u)) {
-            target.for_each(|u| *u = code.value().recode[*u as usize]);
+        if let BioUtilsRecodeSet::DnaComplement = code {
+            return match simd::mut_rev_complement_dna(self.as_mut()) {
+                true => Some(self),
+                false => None,
+            }
+        }
+        let charset = code.charset_table();
+        if self.as_mut().iter().all(|u| charset.contains(*u)) {
+            // Recode before reversing so context-dependent sets see the original neighbours.
+            let target = self.as_mut();
+            recode_in_place(target, &code);
+            target.reverse();
             Some(self)
         } else {
             None
@@ -124,10 +165,94 @@ T: AsRef<[u8]>,
 {
     /// Recode the reverse of self, commonly for generating the reverse complement.
     fn rev_recode(&self, code: BioUtilsRecodeSet) -> Option<Vec<u8>> {
-        if self.is_all_charset_with(code.value().charset) {
-            Some(self.as_ref().iter().rev().map(|u| code.value().recode[*u as usize]).collect::<Vec<u8>>())
+        if let BioUtilsRecodeSet::DnaComplement = code {
+            return simd::rev_complement_dna(self.as_ref())
+        }
+        let charset = code.charset_table();
+        if self.as_ref().iter().all(|u| charset.contains(*u)) {
+            let mut recoded = recoded(self.as_ref(), &code);
+            recoded.reverse();
+            Some(recoded)
         } else {
             None
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recodes_in_place() {
+        let mut seq = b"AACGT".to_vec();
+        assert!(seq.mut_rev_recode(BioUtilsRecodeSet::DnaComplement).is_some());
+        assert_eq!(seq, b"ACGTT");
+        assert_eq!(seq.rev_recode(BioUtilsRecodeSet::DnaComplement).unwrap(), b"AACGT");
+        let mut scores = vec![0u8, 40];
+        assert!(scores.mut_recode(BioUtilsRecodeSet::Phred33Encode).is_some());
+        assert_eq!(scores, b"!I");
+        assert_eq!(b"!K".recode(BioUtilsRecodeSet::Phred33Decode).unwrap(), vec![0u8, 42]);
+        assert!(b"ACGN".to_vec().mut_recode(BioUtilsRecodeSet::DnaComplement).is_none());
+    }
+
+    #[test]
+    fn decodes_every_phred33_byte() {
+        assert_eq!(PHRED33_DECODE[b'K' as usize], 42);
+        assert_eq!(b'K'.recode_u8(BioUtilsRecodeSet::Phred33Decode), Some(42));
+        let encoded: Vec<u8> = (b'!'..=b'K').collect();
+        let scores: Vec<u8> = (0..=42).collect();
+        assert_eq!(encoded.recode(BioUtilsRecodeSet::Phred33Decode).unwrap(), scores);
+    }
+
+    #[test]
+    fn mut_recodes_write_the_buffer() {
+        let mut seq = b"AAAC".to_vec();
+        seq.mut_recode(BioUtilsRecodeSet::DnaComplement).unwrap();
+        assert_eq!(seq, b"TTTG".to_vec());
+        seq.mut_rev_recode(BioUtilsRecodeSet::DnaComplement).unwrap();
+        assert_eq!(seq, b"CAAA".to_vec());
+        let mut qual = b"!5K".to_vec();
+        qual.mut_recode(BioUtilsRecodeSet::Phred33Decode).unwrap();
+        assert_eq!(qual, vec![0u8, 20, 42]);
+    }
+
+    #[test]
+    fn bisulfite_converts() {
+        let seq = b"ACGTCCGgcAgN";
+        assert_eq!(seq.recode(BioUtilsRecodeSet::BisulfiteTop).unwrap(), b"ATGTTTGgtAgN".to_vec());
+        assert_eq!(seq.recode(BioUtilsRecodeSet::BisulfiteBottom).unwrap(), b"ACATCCAacAaN".to_vec());
+        assert_eq!(seq.recode(BioUtilsRecodeSet::BisulfiteTopCpg).unwrap(), b"ACGTTCGgtAgN".to_vec());
+        assert_eq!(seq.recode(BioUtilsRecodeSet::BisulfiteBottomCpg).unwrap(), b"ACGTCCGacAaN".to_vec());
+        let mut in_place = seq.to_vec();
+        assert!(in_place.mut_recode(BioUtilsRecodeSet::BisulfiteBottomCpg).is_some());
+        assert_eq!(in_place, b"ACGTCCGacAaN".to_vec());
+        let mut reversed = seq.to_vec();
+        assert!(reversed.mut_rev_recode(BioUtilsRecodeSet::BisulfiteTopCpg).is_some());
+        assert_eq!(reversed, seq.rev_recode(BioUtilsRecodeSet::BisulfiteTopCpg).unwrap());
+        assert_eq!(reversed, b"NgAtgGCTTGCA".to_vec());
+        assert!(b"ACGU".recode(BioUtilsRecodeSet::BisulfiteTop).is_none());
+    }
+
+    #[test]
+    fn transcribes_and_complements_rna() {
+        assert_eq!(b"ACGTtN".recode(BioUtilsRecodeSet::Transcribe).unwrap(), b"ACGUuN".to_vec());
+        assert_eq!(b"ACGUuN".recode(BioUtilsRecodeSet::ReverseTranscribe).unwrap(), b"ACGTtN".to_vec());
+        assert_eq!(b"AACGU".rev_recode(BioUtilsRecodeSet::RnaComplement).unwrap(), b"ACGUU".to_vec());
+        assert!(b"AACGN".rev_recode(BioUtilsRecodeSet::RnaComplement).is_none());
+        assert_eq!(b"AACGN".rev_recode(BioUtilsRecodeSet::RnanComplement).unwrap(), b"NCGUU".to_vec());
+        assert_eq!(b"aaCgu".rev_recode(BioUtilsRecodeSet::RnaComplementMixCase).unwrap(), b"acGuu".to_vec());
+        assert_eq!(b"nAcg".rev_recode(BioUtilsRecodeSet::RnanComplementMixCase).unwrap(), b"cgUn".to_vec());
+        assert!(b"ACGT".recode(BioUtilsRecodeSet::RnaComplement).is_none());
+    }
+
+    #[test]
+    fn complements_iupac() {
+        assert_eq!(b"ACGTRYKMBVDHSWN-".rev_recode(BioUtilsRecodeSet::IupacComplement).unwrap(), b"-NWSDHBVKMRYACGT".to_vec());
+        assert!(b"ACGTr".rev_recode(BioUtilsRecodeSet::IupacComplement).is_none());
+        assert_eq!(b"acRyn.".rev_recode(BioUtilsRecodeSet::IupacComplementMixCase).unwrap(), b".nrYgt".to_vec());
+        let mut primer = b"GGNCCRY".to_vec();
+        assert!(primer.mut_rev_recode(BioUtilsRecodeSet::IupacComplement).is_some());
+        assert_eq!(primer, b"RYGGNCC".to_vec());
+    }
+}
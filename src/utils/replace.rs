@@ -18,6 +18,7 @@
 //! ```
 
 use super::*;
+use rand::Rng;
 
 /// Takes a BioUtilsCharSet and a ThreadRng and replaces any character not in the charset with a random character from the characterset.
 pub trait CleanAsMutSlice<T> {
@@ -33,12 +34,22 @@ T: AsMut<[u8]>,
 {
     /// Takes a BioUtilsCharSet and a ThreadRng and replaces any character not in the charset with a random character from the characterset.
     fn mut_clean(&mut self, charset: BioUtilsCharSet, mut rng: ThreadRng) -> Result<&mut Self> {
-        self.as_mut().iter_mut().for_each(|c| if charset.value().contains(c) {} else {*c = *charset.value().choose(&mut rng).expect("Could not choose character")});
+        let table = charset.table();
+        for c in self.as_mut().iter_mut() {
+            if !table.contains(*c) {
+                *c = *charset.value().choose(&mut rng).ok_or(BioUtilsError::EmptyCharset)?;
+            }
+        }
         Ok(self)
     }
     /// Takes a character slice and a ThreadRng and replaces any character not in the charset with a random character from the characterset.
     fn mut_clean_with(&mut self, charset: &[u8], mut rng: ThreadRng) -> Result<&mut Self> {
-        self.as_mut().iter_mut().for_each(|c| if charset.contains(c) {} else {*c = *charset.choose(&mut rng).expect("Could not choose character")});
+        let table = BioUtilsByteSet::new(charset);
+        for c in self.as_mut().iter_mut() {
+            if !table.contains(*c) {
+                *c = *charset.choose(&mut rng).ok_or(BioUtilsError::EmptyCharset)?;
+            }
+        }
         Ok(self)
     }
 }
@@ -167,54 +178,20 @@ T: AsMut<[u8]>,
     }
     /// Pseudorandom nucleotide randomments within IUPAC specifications, e.g. R: either A or G. Case specific, r: either a or g.
     fn mut_xna_clean_iupac(&mut self, xna: &str, mut rng: ThreadRng) -> Result<&mut Self> {
-        if xna == "RNA" {
-            self.as_mut().iter_mut().for_each(|c| match c {
-                    b'R' => *c = *R_BASES.choose(&mut rng).expect("Could not choose base"),
-                    b'r' => *c = *R_BASES_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'Y' => *c = *Y_BASES_RNA.choose(&mut rng).expect("Could not choose base"),
-                    b'y' => *c = *Y_BASES_RNA_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'S' => *c = *S_BASES.choose(&mut rng).expect("Could not choose base"),
-                    b's' => *c = *S_BASES_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'W' => *c = *W_BASES_RNA.choose(&mut rng).expect("Could not choose base"),
-                    b'w' => *c = *W_BASES_RNA_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'K' => *c = *K_BASES_RNA.choose(&mut rng).expect("Could not choose base"),
-                    b'k' => *c = *K_BASES_RNA_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'M' => *c = *M_BASES.choose(&mut rng).expect("Could not choose base"),
-                    b'm' => *c = *M_BASES_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'B' => *c = *B_BASES_RNA.choose(&mut rng).expect("Could not choose base"),
-                    b'b' => *c = *B_BASES_RNA_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'D' => *c = *D_BASES_RNA.choose(&mut rng).expect("Could not choose base"),
-                    b'd' => *c = *D_BASES_RNA_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'H' => *c = *H_BASES_RNA.choose(&mut rng).expect("Could not choose base"),
-                    b'h' => *c = *H_BASES_RNA_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'V' => *c = *V_BASES.choose(&mut rng).expect("Could not choose base"),
-                    b'v' => *c = *V_BASES_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    _ => {}
-                })
-        } else if xna == "DNA" {
-            self.as_mut().iter_mut().for_each(|c| match c {
-                    b'R' => *c = *R_BASES.choose(&mut rng).expect("Could not choose base"),
-                    b'r' => *c = *R_BASES_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'Y' => *c = *Y_BASES.choose(&mut rng).expect("Could not choose base"),
-                    b'y' => *c = *Y_BASES_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'S' => *c = *S_BASES.choose(&mut rng).expect("Could not choose base"),
-                    b's' => *c = *S_BASES_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'W' => *c = *W_BASES.choose(&mut rng).expect("Could not choose base"),
-                    b'w' => *c = *W_BASES_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'K' => *c = *K_BASES.choose(&mut rng).expect("Could not choose base"),
-                    b'k' => *c = *K_BASES_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'M' => *c = *M_BASES.choose(&mut rng).expect("Could not choose base"),
-                    b'm' => *c = *M_BASES_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'B' => *c = *B_BASES.choose(&mut rng).expect("Could not choose base"),
-                    b'b' => *c = *B_BASES_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'D' => *c = *D_BASES.choose(&mut rng).expect("Could not choose base"),
-                    b'd' => *c = *D_BASES_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'H' => *c = *H_BASES.choose(&mut rng).expect("Could not choose base"),
-                    b'h' => *c = *H_BASES_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    b'V' => *c = *V_BASES.choose(&mut rng).expect("Could not choose base"),
-                    b'v' => *c = *V_BASES_LOWERCASE.choose(&mut rng).expect("Could not choose base"),
-                    _ => {}
-                })
+        if xna != "RNA" && xna != "DNA" {
+            return Ok(self)
+        }
+        for c in self.as_mut().iter_mut() {
+            let nibble = match IupacNibble::from_byte(*c) {
+                Some(nibble) if nibble.degeneracy() == 2 || nibble.degeneracy() == 3 => nibble,
+                _ => continue,
+            };
+            let base = match nibble.nth_base(rng.gen_range(0..nibble.degeneracy() as usize)) {
+                Some(b'T') if xna == "RNA" => b'U',
+                Some(base) => base,
+                None => return Err(BioUtilsError::EmptyCharset),
+            };
+            *c = if c.is_ascii_lowercase() { base.to_ascii_lowercase() } else { base };
         }
         Ok(self)
     }
@@ -304,3 +281,18 @@ where
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cleans_iupac_within_code() {
+        let mut seq = b"RrYyBbNn".to_vec();
+        seq.mut_xna_clean_iupac("RNA", rand::thread_rng()).unwrap();
+        assert!(b"AGag".contains(&seq[0]) && b"AGag".contains(&seq[1]));
+        assert!(seq[1].is_ascii_lowercase());
+        assert!(b"CU".contains(&seq[2]) && b"cu".contains(&seq[3]));
+        assert!(b"CUG".contains(&seq[4]) && b"cug".contains(&seq[5]));
+        assert_eq!(&seq[6..], b"Nn");
+    }
+}
@@ -0,0 +1,368 @@
+//! SIMD paths for the hottest sequence operations: DNA validation, GC counting and reverse complementing.
+//! AVX2 or SSE2 is chosen at runtime on x86/x86_64, with a scalar fallback on other targets. The charset and recode traits call these automatically for Dna, DnaMixCase, Dnan and DnaComplement.
+//! ```
+//! use bioutils::utils::simd::*;
+//! let seq = b"ACGTACGTGGCCAATT";
+//! assert!(is_all_dna(seq));
+//! assert_eq!(count_gc(seq), 8);
+//! assert_eq!(rev_complement_dna(seq).unwrap(), b"AATTGGCCACGTACGT");
+//! let mut in_place = seq.to_vec();
+//! assert!(mut_rev_complement_dna(&mut in_place));
+//! assert_eq!(in_place, b"AATTGGCCACGTACGT".to_vec());
+//! ```
+
+use super::*;
+
+#[cfg(target_arch = "x86")]
+use std::arch::x86::*;
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+const DNA_SET: [u8; 4] = [b'A', b'C', b'G', b'T'];
+const DNA_MIX_CASE_SET: [u8; 8] = [b'A', b'C', b'G', b'T', b'a', b'c', b'g', b't'];
+const DNAN_SET: [u8; 5] = [b'A', b'C', b'G', b'T', b'N'];
+const GC_MIX_CASE_SET: [u8; 4] = [b'G', b'C', b'g', b'c'];
+
+/// Checks if every byte is one of ACGT.
+pub fn is_all_dna(seq: &[u8]) -> bool {
+    is_all_of(seq, &DNA_SET)
+}
+
+/// Checks if every byte is one of ACGTacgt.
+pub fn is_all_dna_mix_case(seq: &[u8]) -> bool {
+    is_all_of(seq, &DNA_MIX_CASE_SET)
+}
+
+/// Checks if every byte is one of ACGTN.
+pub fn is_all_dnan(seq: &[u8]) -> bool {
+    is_all_of(seq, &DNAN_SET)
+}
+
+/// Counts the G and C bases, upper or lower case.
+pub fn count_gc(seq: &[u8]) -> usize {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx2") {
+            return unsafe { count_of_avx2(seq, &GC_MIX_CASE_SET) }
+        }
+        if is_x86_feature_detected!("sse2") {
+            return unsafe { count_of_sse2(seq, &GC_MIX_CASE_SET) }
+        }
+    }
+    count_of_scalar(seq, &GC_MIX_CASE_SET)
+}
+
+/// Reverse complement of an ACGT sequence, or None if any byte is not ACGT.
+pub fn rev_complement_dna(seq: &[u8]) -> Option<Vec<u8>> {
+    if !is_all_dna(seq) {
+        return None
+    }
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx2") {
+            return Some(unsafe { rev_complement_avx2(seq) })
+        }
+        if is_x86_feature_detected!("sse2") {
+            return Some(unsafe { rev_complement_sse2(seq) })
+        }
+    }
+    Some(rev_complement_scalar(seq))
+}
+
+/// Reverse complements an ACGT sequence in place, without allocating. Returns false, leaving seq unchanged, if any byte is not ACGT.
+pub fn mut_rev_complement_dna(seq: &mut [u8]) -> bool {
+    if !is_all_dna(seq) {
+        return false
+    }
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx2") {
+            unsafe { mut_rev_complement_avx2(seq) };
+            return true
+        }
+        if is_x86_feature_detected!("sse2") {
+            unsafe { mut_rev_complement_sse2(seq) };
+            return true
+        }
+    }
+    mut_rev_complement_scalar(seq);
+    true
+}
+
+fn is_all_of(seq: &[u8], set: &[u8]) -> bool {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if is_x86_feature_detected!("avx2") {
+            return unsafe { is_all_of_avx2(seq, set) }
+        }
+        if is_x86_feature_detected!("sse2") {
+            return unsafe { is_all_of_sse2(seq, set) }
+        }
+    }
+    is_all_of_scalar(seq, set)
+}
+
+fn is_all_of_scalar(seq: &[u8], set: &[u8]) -> bool {
+    let table = BioUtilsByteSet::new(set);
+    seq.iter().all(|u| table.contains(*u))
+}
+
+fn count_of_scalar(seq: &[u8], set: &[u8]) -> usize {
+    let table = BioUtilsByteSet::new(set);
+    seq.iter().filter(|u| table.contains(**u)).count()
+}
+
+fn rev_complement_scalar(seq: &[u8]) -> Vec<u8> {
+    let recode = BioUtilsRecodeSet::DnaComplement.recode_table();
+    seq.iter().rev().map(|u| recode[*u as usize]).collect()
+}
+
+// Swaps the complements of bytes from both ends towards the middle.
+fn mut_rev_complement_scalar(seq: &mut [u8]) {
+    let recode = BioUtilsRecodeSet::DnaComplement.recode_table();
+    let len = seq.len();
+    for i in 0..len / 2 {
+        let (front, back) = (recode[seq[i] as usize], recode[seq[len - 1 - i] as usize]);
+        seq[i] = back;
+        seq[len - 1 - i] = front;
+    }
+    if len % 2 == 1 {
+        seq[len / 2] = recode[seq[len / 2] as usize];
+    }
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse2")]
+unsafe fn matches_sse2(v: __m128i, set: &[u8]) -> __m128i {
+    let mut hit = _mm_setzero_si128();
+    for u in set {
+        hit = _mm_or_si128(hit, _mm_cmpeq_epi8(v, _mm_set1_epi8(*u as i8)));
+    }
+    hit
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn matches_avx2(v: __m256i, set: &[u8]) -> __m256i {
+    let mut hit = _mm256_setzero_si256();
+    for u in set {
+        hit = _mm256_or_si256(hit, _mm256_cmpeq_epi8(v, _mm256_set1_epi8(*u as i8)));
+    }
+    hit
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse2")]
+unsafe fn is_all_of_sse2(seq: &[u8], set: &[u8]) -> bool {
+    let chunks = seq.chunks_exact(16);
+    let rest = chunks.remainder();
+    for chunk in chunks {
+        let v = _mm_loadu_si128(chunk.as_ptr() as *const __m128i);
+        if _mm_movemask_epi8(matches_sse2(v, set)) != 0xffff {
+            return false
+        }
+    }
+    is_all_of_scalar(rest, set)
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn is_all_of_avx2(seq: &[u8], set: &[u8]) -> bool {
+    let chunks = seq.chunks_exact(32);
+    let rest = chunks.remainder();
+    for chunk in chunks {
+        let v = _mm256_loadu_si256(chunk.as_ptr() as *const __m256i);
+        if _mm256_movemask_epi8(matches_avx2(v, set)) != -1 {
+            return false
+        }
+    }
+    is_all_of_scalar(rest, set)
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse2")]
+unsafe fn count_of_sse2(seq: &[u8], set: &[u8]) -> usize {
+    let chunks = seq.chunks_exact(16);
+    let rest = chunks.remainder();
+    let mut count = 0;
+    for chunk in chunks {
+        let v = _mm_loadu_si128(chunk.as_ptr() as *const __m128i);
+        count += _mm_movemask_epi8(matches_sse2(v, set)).count_ones() as usize;
+    }
+    count + count_of_scalar(rest, set)
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn count_of_avx2(seq: &[u8], set: &[u8]) -> usize {
+    let chunks = seq.chunks_exact(32);
+    let rest = chunks.remainder();
+    let mut count = 0;
+    for chunk in chunks {
+        let v = _mm256_loadu_si256(chunk.as_ptr() as *const __m256i);
+        count += _mm256_movemask_epi8(matches_avx2(v, set)).count_ones() as usize;
+    }
+    count + count_of_scalar(rest, set)
+}
+
+// A <-> T is xor 0x15 and C <-> G is xor 0x04, so the complement is an xor picked by comparing against A and T.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse2")]
+unsafe fn rev_complement_vector_sse2(v: __m128i) -> __m128i {
+    let at = _mm_or_si128(_mm_cmpeq_epi8(v, _mm_set1_epi8(b'A' as i8)), _mm_cmpeq_epi8(v, _mm_set1_epi8(b'T' as i8)));
+    let mask = _mm_or_si128(_mm_and_si128(at, _mm_set1_epi8(0x15)), _mm_andnot_si128(at, _mm_set1_epi8(0x04)));
+    let v = _mm_xor_si128(v, mask);
+    // Reverse the 16 bytes: 32-bit words, then 16-bit halves, then bytes.
+    let v = _mm_shuffle_epi32(v, 0x1b);
+    let v = _mm_shufflehi_epi16(_mm_shufflelo_epi16(v, 0xb1), 0xb1);
+    _mm_or_si128(_mm_slli_epi16(v, 8), _mm_srli_epi16(v, 8))
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn rev_complement_vector_avx2(v: __m256i) -> __m256i {
+    let reverse = _mm256_setr_epi8(15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0, 15, 14, 13, 12, 11, 10, 9, 8, 7, 6, 5, 4, 3, 2, 1, 0);
+    let at = _mm256_or_si256(_mm256_cmpeq_epi8(v, _mm256_set1_epi8(b'A' as i8)), _mm256_cmpeq_epi8(v, _mm256_set1_epi8(b'T' as i8)));
+    let mask = _mm256_blendv_epi8(_mm256_set1_epi8(0x04), _mm256_set1_epi8(0x15), at);
+    let v = _mm256_xor_si256(v, mask);
+    // Reverse the bytes within each 128-bit lane, then swap the lanes.
+    let v = _mm256_shuffle_epi8(v, reverse);
+    _mm256_permute2x128_si256(v, v, 0x01)
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse2")]
+unsafe fn rev_complement_sse2(seq: &[u8]) -> Vec<u8> {
+    let len = seq.len();
+    let mut out = vec![0u8; len];
+    let chunks = seq.chunks_exact(16);
+    let rest = chunks.remainder();
+    for (i, chunk) in chunks.enumerate() {
+        let v = rev_complement_vector_sse2(_mm_loadu_si128(chunk.as_ptr() as *const __m128i));
+        _mm_storeu_si128(out[len - 16 * (i + 1)..].as_mut_ptr() as *mut __m128i, v);
+    }
+    out[..rest.len()].copy_from_slice(&rev_complement_scalar(rest));
+    out
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn rev_complement_avx2(seq: &[u8]) -> Vec<u8> {
+    let len = seq.len();
+    let mut out = vec![0u8; len];
+    let chunks = seq.chunks_exact(32);
+    let rest = chunks.remainder();
+    for (i, chunk) in chunks.enumerate() {
+        let v = rev_complement_vector_avx2(_mm256_loadu_si256(chunk.as_ptr() as *const __m256i));
+        _mm256_storeu_si256(out[len - 32 * (i + 1)..].as_mut_ptr() as *mut __m256i, v);
+    }
+    out[..rest.len()].copy_from_slice(&rev_complement_scalar(rest));
+    out
+}
+
+// Swaps reverse complemented 16 byte chunks from both ends, then the middle of less than 32 bytes with the scalar path.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "sse2")]
+unsafe fn mut_rev_complement_sse2(seq: &mut [u8]) {
+    let len = seq.len();
+    let pairs = len / 32;
+    let ptr = seq.as_mut_ptr();
+    for i in 0..pairs {
+        let front = ptr.add(16 * i) as *mut __m128i;
+        let back = ptr.add(len - 16 * (i + 1)) as *mut __m128i;
+        let (f, b) = (_mm_loadu_si128(front), _mm_loadu_si128(back));
+        _mm_storeu_si128(front, rev_complement_vector_sse2(b));
+        _mm_storeu_si128(back, rev_complement_vector_sse2(f));
+    }
+    mut_rev_complement_scalar(&mut seq[16 * pairs..len - 16 * pairs]);
+}
+
+// Swaps reverse complemented 32 byte chunks from both ends, then the middle of less than 64 bytes with the scalar path.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[target_feature(enable = "avx2")]
+unsafe fn mut_rev_complement_avx2(seq: &mut [u8]) {
+    let len = seq.len();
+    let pairs = len / 64;
+    let ptr = seq.as_mut_ptr();
+    for i in 0..pairs {
+        let front = ptr.add(32 * i) as *mut __m256i;
+        let back = ptr.add(len - 32 * (i + 1)) as *mut __m256i;
+        let (f, b) = (_mm256_loadu_si256(front), _mm256_loadu_si256(back));
+        _mm256_storeu_si256(front, rev_complement_vector_avx2(b));
+        _mm256_storeu_si256(back, rev_complement_vector_avx2(f));
+    }
+    mut_rev_complement_scalar(&mut seq[32 * pairs..len - 32 * pairs]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+    use crate::utils::new::RandomBioVec;
+
+    #[test]
+    fn matches_scalar() {
+        for len in [0usize, 1, 15, 16, 17, 31, 32, 33, 63, 64, 65, 97, 100, 1000].iter() {
+            let seq = Vec::<u8>::random_vec(len, BioUtilsCharSet::Dna).unwrap();
+            assert!(is_all_dna(&seq) && is_all_dnan(&seq) && is_all_dna_mix_case(&seq));
+            assert_eq!(count_gc(&seq), count_of_scalar(&seq, &GC_MIX_CASE_SET));
+            assert_eq!(rev_complement_dna(&seq).unwrap(), rev_complement_scalar(&seq));
+            let mut in_place = seq.clone();
+            assert!(mut_rev_complement_dna(&mut in_place));
+            assert_eq!(in_place, rev_complement_scalar(&seq));
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            unsafe {
+                assert!(is_all_of_sse2(&seq, &DNA_SET));
+                assert_eq!(count_of_sse2(&seq, &GC_MIX_CASE_SET), count_of_scalar(&seq, &GC_MIX_CASE_SET));
+                assert_eq!(rev_complement_sse2(&seq), rev_complement_scalar(&seq));
+                let mut in_place = seq.clone();
+                mut_rev_complement_sse2(&mut in_place);
+                assert_eq!(in_place, rev_complement_scalar(&seq));
+            }
+            let mut in_place = seq.clone();
+            mut_rev_complement_scalar(&mut in_place);
+            assert_eq!(in_place, rev_complement_scalar(&seq));
+            if *len > 0 {
+                let mut bad = seq.clone();
+                bad[len - 1] = b'N';
+                assert!(!is_all_dna(&bad) && is_all_dnan(&bad));
+                assert!(rev_complement_dna(&bad).is_none());
+                assert!(!mut_rev_complement_dna(&mut bad));
+                assert_eq!(bad[len - 1], b'N');
+            }
+        }
+    }
+
+    // Keeps the optimiser from removing the benchmarked call, std::hint::black_box needs Rust 1.66.
+    fn black_box<T>(x: T) -> T {
+        unsafe {
+            let y = std::ptr::read_volatile(&x);
+            std::mem::forget(x);
+            y
+        }
+    }
+
+    // Benchmarks against the scalar path, run with: cargo test --release -- --ignored --nocapture bench
+    fn bench<T, F: FnMut() -> T>(name: &str, mut f: F) {
+        let start = Instant::now();
+        for _ in 0..20 {
+            black_box(f());
+        }
+        println!("{}: {:?} per iteration", name, start.elapsed() / 20);
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_simd_against_scalar() {
+        let seq = Vec::<u8>::random_vec(&10_000_000, BioUtilsCharSet::Dna).unwrap();
+        bench("is_all_dna simd", || is_all_dna(black_box(&seq)));
+        bench("is_all_dna scalar", || is_all_of_scalar(black_box(&seq), &DNA_SET));
+        bench("count_gc simd", || count_gc(black_box(&seq)));
+        bench("count_gc scalar", || count_of_scalar(black_box(&seq), &GC_MIX_CASE_SET));
+        bench("rev_complement_dna simd", || rev_complement_dna(black_box(&seq)));
+        bench("rev_complement_dna scalar", || rev_complement_scalar(black_box(&seq)));
+        let mut in_place = seq.clone();
+        bench("mut_rev_complement_dna simd", || mut_rev_complement_dna(black_box(&mut in_place)));
+        bench("mut_rev_complement_dna scalar", || mut_rev_complement_scalar(black_box(&mut in_place)));
+    }
+}
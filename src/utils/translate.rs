@@ -0,0 +1,104 @@
+//! Translate DNA or RNA into protein with any NCBI genetic code. Frames 1, 2, 3 read the forward strand from the first, second or third base and -1, -2, -3 the reverse complement.
+//! # Examples
+//! ```
+//! use bioutils::charsets::codon::GeneticCode;
+//! use bioutils::utils::translate::TranslateAsRefSlice;
+//! let seq = b"ATGGCCTGAAAGTAA";
+//! assert_eq!(seq.translate(GeneticCode::Standard), b"MA*K*".to_vec());
+//! assert_eq!(seq.translate(GeneticCode::VertebrateMitochondrial), b"MAWK*".to_vec());
+//! assert_eq!(seq.translate_frame(GeneticCode::Standard, -1).unwrap(), b"LLSGH".to_vec());
+//! assert_eq!(seq.translate_cds(GeneticCode::Standard).unwrap(), b"MA".to_vec());
+//! // Alternative start codons translate as M at the start of a CDS
+//! assert_eq!(b"GTGAAATAG".translate_cds(GeneticCode::Bacterial).unwrap(), b"MK".to_vec());
+//! ```
+
+use super::*;
+
+// Reverse complement of IUPAC nucleotides, other bytes are kept so they translate to X.
+fn rev_complement_iupac(seq: &[u8]) -> Vec<u8> {
+    seq.iter().rev().map(|u| IupacNibble::from_byte(*u).map_or(*u, |nibble| nibble.complement().to_byte())).collect()
+}
+
+// Start of the frame in the forward or reverse complement sequence.
+fn frame_offset(frame: i8) -> Result<usize> {
+    match frame {
+        1..=3 => Ok(frame as usize - 1),
+        -3..=-1 => Ok((-frame) as usize - 1),
+        _ => Err(BioUtilsError::InvalidFrame(frame)),
+    }
+}
+
+pub trait TranslateAsRefSlice<T> {
+    /// Translates every complete codon from the first base. Stops are *, codons that cannot be resolved are X.
+    fn translate(&self, code: GeneticCode) -> Vec<u8>;
+    /// Translates the frame, 1, 2, 3 on the forward strand or -1, -2, -3 on the reverse complement.
+    fn translate_frame(&self, code: GeneticCode, frame: i8) -> Result<Vec<u8>>;
+    /// Translates all six frames in the order 1, 2, 3, -1, -2, -3.
+    fn translate_six_frames(&self, code: GeneticCode) -> [Vec<u8>; 6];
+    /// Translates from the first base up to, not including, the first stop.
+    fn translate_to_stop(&self, code: GeneticCode) -> Vec<u8>;
+    /// Translates a coding sequence that begins with a start codon of the code, which is read as M even for alternative starts such as GTG. Translation ends before the first stop.
+    fn translate_cds(&self, code: GeneticCode) -> Result<Vec<u8>>;
+}
+
+impl<T> TranslateAsRefSlice<T> for T where
+T: AsRef<[u8]>,
+{
+    /// Translates every complete codon from the first base. Stops are *, codons that cannot be resolved are X.
+    fn translate(&self, code: GeneticCode) -> Vec<u8> {
+        self.as_ref().chunks_exact(3).map(|codon| code.translate_codon(codon)).collect()
+    }
+    /// Translates the frame, 1, 2, 3 on the forward strand or -1, -2, -3 on the reverse complement.
+    fn translate_frame(&self, code: GeneticCode, frame: i8) -> Result<Vec<u8>> {
+        let offset = frame_offset(frame)?;
+        let seq = self.as_ref();
+        if frame > 0 {
+            return Ok(seq.get(offset..).unwrap_or_default().translate(code))
+        }
+        let rev = rev_complement_iupac(seq);
+        Ok(rev.get(offset..).unwrap_or_default().translate(code))
+    }
+    /// Translates all six frames in the order 1, 2, 3, -1, -2, -3.
+    fn translate_six_frames(&self, code: GeneticCode) -> [Vec<u8>; 6] {
+        let seq = self.as_ref();
+        let rev = rev_complement_iupac(seq);
+        let frame = |s: &[u8], offset: usize| s.get(offset..).unwrap_or_default().translate(code);
+        [frame(seq, 0), frame(seq, 1), frame(seq, 2), frame(&rev, 0), frame(&rev, 1), frame(&rev, 2)]
+    }
+    /// Translates from the first base up to, not including, the first stop.
+    fn translate_to_stop(&self, code: GeneticCode) -> Vec<u8> {
+        self.as_ref().chunks_exact(3).map(|codon| code.translate_codon(codon)).take_while(|residue| *residue != STOP).collect()
+    }
+    /// Translates a coding sequence that begins with a start codon of the code, which is read as M even for alternative starts such as GTG. Translation ends before the first stop.
+    fn translate_cds(&self, code: GeneticCode) -> Result<Vec<u8>> {
+        let seq = self.as_ref();
+        match seq.get(..3) {
+            Some(start) if code.is_start(start) => {
+                let mut protein = vec![b'M'];
+                protein.extend((&seq[3..]).translate_to_stop(code));
+                Ok(protein)
+            }
+            _ => Err(BioUtilsError::NoStartCodon),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translates_frames() {
+        let seq = b"AUGCCRAAYTTTTGA";
+        assert_eq!(seq.translate(GeneticCode::Standard), b"MPNF*".to_vec());
+        let frames = b"ATGAAACCC".translate_six_frames(GeneticCode::Standard);
+        assert_eq!(frames[0], b"MKP".to_vec());
+        assert_eq!(frames[1], b"*N".to_vec());
+        assert_eq!(frames[3], b"GFH".to_vec());
+        assert_eq!(frames[4], b"GF".to_vec());
+        assert_eq!(frames[4], b"ATGAAACCC".translate_frame(GeneticCode::Standard, -2).unwrap());
+        assert!(seq.translate_frame(GeneticCode::Standard, 4).is_err());
+        assert!(b"CCCATG".translate_cds(GeneticCode::Standard).is_err());
+        assert_eq!(b"TTGAAA".translate_cds(GeneticCode::Standard).unwrap(), b"MK".to_vec());
+    }
+}
@@ -0,0 +1,154 @@
+//! Quality trimming of reads. Trimmers take Phred scores (decode qualities first, e.g. with BioUtilsRecodeSet::Phred33Decode) and return the range to keep, so the sequence and quality can be cut together.
+//! # Examples
+//! ```
+//! use bioutils::utils::trim::*;
+//! let scores = [2u8, 30, 32, 35, 34, 30, 12, 10, 31, 8, 2];
+//! // Trimmomatic LEADING:3 TRAILING:3
+//! assert_eq!(scores.trim_leading(&3), 1..11);
+//! assert_eq!(scores.trim_trailing(&3), 0..10);
+//! // Trimmomatic SLIDINGWINDOW:3:20
+//! assert_eq!(scores.trim_sliding_window(3, &20), 0..6);
+//! // bwa -q 20
+//! assert_eq!(scores.trim_mott(&20), 0..6);
+//! // Steps run in order, and the read is dropped if it ends up shorter than MinLength
+//! let steps = [TrimStep::Leading(3), TrimStep::SlidingWindow { window: 3, min_mean: 20 }, TrimStep::MinLength(5)];
+//! let range = scores.trim(&steps).unwrap().unwrap();
+//! assert_eq!(range, 1..6);
+//! let seq = b"ACGTACGTACG";
+//! assert_eq!(&seq[range], b"CGTAC");
+//! assert_eq!(scores.trim(&[TrimStep::MinLength(20)]).unwrap(), None);
+//! // Percents above 100 are an error rather than dropping every read
+//! assert!(scores.trim(&[TrimStep::MinPercentGe { quality: 20, percent: 150 }]).is_err());
+//! ```
+
+use super::*;
+use std::ops::Range;
+use crate::utils::arithmetic::ArithmeticAsRefSlice;
+use crate::utils::count::PercentAsRefOrdSlice;
+use crate::utils::tryfrom::PercentUsize;
+
+/// A trimming or filtering step, see TrimAsRefSlice::trim.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrimStep {
+    /// Removes leading bases below the quality, Trimmomatic LEADING.
+    Leading(u8),
+    /// Removes trailing bases below the quality, Trimmomatic TRAILING.
+    Trailing(u8),
+    /// Cuts the read once the mean quality of a window drops below min_mean, Trimmomatic SLIDINGWINDOW.
+    SlidingWindow { window: usize, min_mean: u8 },
+    /// Trims the 3' end with the Mott algorithm, bwa -q.
+    Mott(u8),
+    /// Drops reads shorter than the length, Trimmomatic MINLEN.
+    MinLength(usize),
+    /// Drops reads where fewer than percent of the bases have at least the quality.
+    MinPercentGe { quality: u8, percent: usize },
+}
+
+// Range within the slice to a range within the parent range.
+fn within(parent: &Range<usize>, range: Range<usize>) -> Range<usize> {
+    parent.start + range.start..parent.start + range.end
+}
+
+pub trait TrimAsRefSlice<T> {
+    /// Returns the range without leading scores below min_quality.
+    fn trim_leading(&self, min_quality: &u8) -> Range<usize>;
+    /// Returns the range without trailing scores below min_quality.
+    fn trim_trailing(&self, min_quality: &u8) -> Range<usize>;
+    /// Scans windows from the 5' end and cuts at the first window with a mean below min_mean, keeping the bases before the last of that window that are at least min_mean. Reads shorter than the window are one window.
+    fn trim_sliding_window(&self, window: usize, min_mean: &u8) -> Range<usize>;
+    /// Returns the range that trims the 3' end at the position maximising the sum of threshold minus score, the Mott algorithm as in bwa -q.
+    fn trim_mott(&self, threshold: &u8) -> Range<usize>;
+    /// Applies the steps in order and returns the range to keep, or None if a filter step drops the read. Errors if a MinPercentGe percent is above 100.
+    fn trim(&self, steps: &[TrimStep]) -> Result<Option<Range<usize>>>;
+}
+
+impl<T> TrimAsRefSlice<T> for T where
+T: AsRef<[u8]>,
+{
+    /// Returns the range without leading scores below min_quality.
+    fn trim_leading(&self, min_quality: &u8) -> Range<usize> {
+        let scores = self.as_ref();
+        let start = scores.iter().position(|q| q >= min_quality).unwrap_or(scores.len());
+        start..scores.len()
+    }
+    /// Returns the range without trailing scores below min_quality.
+    fn trim_trailing(&self, min_quality: &u8) -> Range<usize> {
+        let end = self.as_ref().iter().rposition(|q| q >= min_quality).map_or(0, |i| i + 1);
+        0..end
+    }
+    /// Scans windows from the 5' end and cuts at the first window with a mean below min_mean, keeping the bases before the last of that window that are at least min_mean. Reads shorter than the window are one window.
+    fn trim_sliding_window(&self, window: usize, min_mean: &u8) -> Range<usize> {
+        let scores = self.as_ref();
+        let window = window.clamp(1, scores.len().max(1));
+        match scores.windows(window).position(|w| w.usize_mean() < usize::from(*min_mean)) {
+            Some(start) => (&scores[..start + window - 1]).trim_trailing(min_mean),
+            None => 0..scores.len(),
+        }
+    }
+    /// Returns the range that trims the 3' end at the position maximising the sum of threshold minus score, the Mott algorithm as in bwa -q.
+    fn trim_mott(&self, threshold: &u8) -> Range<usize> {
+        let scores = self.as_ref();
+        let (mut sum, mut max, mut end) = (0i64, 0i64, scores.len());
+        for (i, q) in scores.iter().enumerate().rev() {
+            sum += i64::from(*threshold) - i64::from(*q);
+            if sum < 0 {
+                break
+            }
+            if sum > max {
+                max = sum;
+                end = i;
+            }
+        }
+        0..end
+    }
+    /// Applies the steps in order and returns the range to keep, or None if a filter step drops the read. Errors if a MinPercentGe percent is above 100.
+    fn trim(&self, steps: &[TrimStep]) -> Result<Option<Range<usize>>> {
+        for step in steps.iter() {
+            if let TrimStep::MinPercentGe { percent, .. } = step {
+                PercentUsize::try_from(percent)?;
+            }
+        }
+        let scores = self.as_ref();
+        let mut range = 0..scores.len();
+        for step in steps.iter() {
+            let kept = &scores[range.clone()];
+            range = match *step {
+                TrimStep::Leading(quality) => within(&range, kept.trim_leading(&quality)),
+                TrimStep::Trailing(quality) => within(&range, kept.trim_trailing(&quality)),
+                TrimStep::SlidingWindow { window, min_mean } => within(&range, kept.trim_sliding_window(window, &min_mean)),
+                TrimStep::Mott(threshold) => within(&range, kept.trim_mott(&threshold)),
+                TrimStep::MinLength(length) if kept.len() < length => return Ok(None),
+                TrimStep::MinPercentGe { quality, percent } if !kept.is_percent_ge(&quality, &percent)? => return Ok(None),
+                TrimStep::MinLength(_) | TrimStep::MinPercentGe { .. } => range,
+            };
+        }
+        Ok(Some(range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trims_scores() {
+        let low = [2u8, 2, 2];
+        assert_eq!(low.trim_leading(&3), 3..3);
+        assert_eq!(low.trim_trailing(&3), 0..0);
+        assert_eq!(low.trim_sliding_window(4, &20), 0..0);
+        assert_eq!(low.trim_mott(&20), 0..0);
+        let high = [30u8; 10];
+        assert_eq!(high.trim_sliding_window(4, &20), 0..10);
+        assert_eq!(high.trim_mott(&20), 0..10);
+        // A single bad base is kept by Mott trimming if the bases after it outweigh it
+        assert_eq!([30u8, 30, 5, 30, 30, 2].trim_mott(&20), 0..5);
+        let scores = [30u8, 30, 30, 30, 10, 10, 10, 10];
+        assert_eq!(scores.trim(&[TrimStep::MinPercentGe { quality: 20, percent: 60 }]).unwrap(), None);
+        assert_eq!(scores.trim(&[TrimStep::Trailing(20), TrimStep::MinPercentGe { quality: 20, percent: 60 }]).unwrap(), Some(0..4));
+        // An empty read has no bases at the quality, so only a 0 percent filter keeps it
+        assert_eq!(scores.trim(&[TrimStep::Leading(40), TrimStep::MinPercentGe { quality: 20, percent: 1 }]).unwrap(), None);
+        assert_eq!(scores.trim(&[TrimStep::Leading(40), TrimStep::MinPercentGe { quality: 20, percent: 0 }]).unwrap(), Some(8..8));
+        assert_eq!(b"".trim(&[TrimStep::SlidingWindow { window: 4, min_mean: 20 }, TrimStep::Mott(20)]).unwrap(), Some(0..0));
+        assert!(matches!(high.trim(&[TrimStep::MinLength(5), TrimStep::MinPercentGe { quality: 20, percent: 150 }]), Err(BioUtilsError::PercentOutOfRange(150))));
+    }
+}